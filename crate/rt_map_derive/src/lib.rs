@@ -0,0 +1,225 @@
+//! Derive macros for the [`rt_map`] crate.
+//!
+//! These are re-exported by `rt_map` when the `"derive"` feature is enabled,
+//! and should be used through that crate.
+//!
+//! [`rt_map`]: https://crates.io/crates/rt_map
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Error, Expr, ExprLit, Fields, GenericArgument, GenericParam, Lit,
+    PathArguments, Type, parse_macro_input, parse_quote, spanned::Spanned,
+};
+
+/// Derives `RtAccess` for a struct of `Ref` and `RefMut` fields.
+///
+/// The struct must have exactly one lifetime parameter, and be annotated
+/// with the map's key type. Each field must be annotated with the key to
+/// borrow it from. The implementation is generic over the map's hasher.
+///
+/// ```rust,ignore
+/// use rt_map::{Ref, RefMut, RtAccess};
+///
+/// #[derive(RtAccess)]
+/// #[rt_access(key_type = char)]
+/// struct Data<'a> {
+///     #[rt_access(key = 'a')]
+///     a: Ref<'a, u32>,
+///     #[rt_access(key = 'b')]
+///     b: RefMut<'a, u32>,
+/// }
+/// ```
+#[proc_macro_derive(RtAccess, attributes(rt_access))]
+pub fn rt_access_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    rt_access_impl(ast)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn rt_access_impl(ast: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let ident = &ast.ident;
+    let (_, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    // The map's hasher is an extra parameter on the impl, not on the struct.
+    let mut impl_generics_with_hasher = ast.generics.clone();
+    impl_generics_with_hasher.params.push(GenericParam::Type(
+        parse_quote!(S: ::core::hash::BuildHasher),
+    ));
+    let (impl_generics, _, _) = impl_generics_with_hasher.split_for_impl();
+
+    let mut lifetimes = ast.generics.lifetimes();
+    let lifetime = match (lifetimes.next(), lifetimes.next()) {
+        (Some(lifetime_param), None) => &lifetime_param.lifetime,
+        _ => {
+            return Err(Error::new(
+                ast.generics.span(),
+                "`RtAccess` requires the struct to have exactly one lifetime parameter.",
+            ));
+        }
+    };
+
+    let key_type = key_type(&ast)?;
+
+    let fields = match &ast.data {
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Named(fields_named) => &fields_named.named,
+            _ => {
+                return Err(Error::new(
+                    ident.span(),
+                    "`RtAccess` can only be derived for structs with named fields.",
+                ));
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                ident.span(),
+                "`RtAccess` can only be derived for structs.",
+            ));
+        }
+    };
+
+    let mut value_type = None;
+    let field_fetches = fields
+        .iter()
+        .map(|field| {
+            let field_ident = field.ident.as_ref().expect("Named fields have idents.");
+            let (borrow_kind, field_value_type) = borrow_kind_and_value_type(&field.ty)?;
+            value_type.get_or_insert(field_value_type);
+
+            let key = field_key(field)?;
+            let fetch = match borrow_kind {
+                BorrowKind::Ref => quote!(rt_map.try_borrow(#key)?),
+                BorrowKind::RefMut => quote!(rt_map.try_borrow_mut(#key)?),
+            };
+
+            Ok(quote!(#field_ident: #fetch))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let value_type = value_type.ok_or_else(|| {
+        Error::new(
+            ident.span(),
+            "`RtAccess` requires at least one `Ref` or `RefMut` field.",
+        )
+    })?;
+
+    Ok(quote! {
+        impl #impl_generics ::rt_map::RtAccess<#lifetime, #key_type, #value_type, S>
+            for #ident #ty_generics
+        #where_clause
+        {
+            fn fetch(
+                rt_map: &#lifetime ::rt_map::RtMap<#key_type, #value_type, S>,
+            ) -> ::core::result::Result<Self, ::rt_map::BorrowFail> {
+                ::core::result::Result::Ok(Self {
+                    #(#field_fetches),*
+                })
+            }
+        }
+    })
+}
+
+/// Whether a field is an immutable or mutable borrow.
+enum BorrowKind {
+    Ref,
+    RefMut,
+}
+
+/// Returns the key type from the `#[rt_access(key_type = ..)]` attribute.
+fn key_type(ast: &DeriveInput) -> Result<Type, Error> {
+    let mut key_type = None;
+    for attr in ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("rt_access"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key_type") {
+                key_type = Some(meta.value()?.parse::<Type>()?);
+                Ok(())
+            } else {
+                Err(meta.error("Expected `key_type = ..`."))
+            }
+        })?;
+    }
+
+    key_type.ok_or_else(|| {
+        Error::new(
+            ast.ident.span(),
+            "`RtAccess` requires the map's key type: `#[rt_access(key_type = ..)]`.",
+        )
+    })
+}
+
+/// Returns the tokens to pass to `try_borrow` for a field's key.
+///
+/// String literals are passed as is, so that they can be used to look up
+/// `String` keys. Other expressions are passed by reference.
+fn field_key(field: &syn::Field) -> Result<proc_macro2::TokenStream, Error> {
+    let mut key = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("rt_access"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                key = Some(meta.value()?.parse::<Expr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("Expected `key = ..`."))
+            }
+        })?;
+    }
+
+    match key {
+        Some(
+            key @ Expr::Lit(ExprLit {
+                lit: Lit::Str(_), ..
+            }),
+        ) => Ok(quote!(#key)),
+        Some(key) => Ok(quote!(&#key)),
+        None => Err(Error::new(
+            field.span(),
+            "`RtAccess` fields require a key: `#[rt_access(key = ..)]`.",
+        )),
+    }
+}
+
+/// Returns whether the field type is a `Ref` or `RefMut`, and its value type.
+fn borrow_kind_and_value_type(ty: &Type) -> Result<(BorrowKind, &Type), Error> {
+    let error = || {
+        Error::new(
+            ty.span(),
+            "`RtAccess` fields must be `Ref<'a, V>` or `RefMut<'a, V>`.",
+        )
+    };
+
+    let Type::Path(type_path) = ty else {
+        return Err(error());
+    };
+    let segment = type_path.path.segments.last().ok_or_else(error)?;
+    let borrow_kind = if segment.ident == "Ref" {
+        BorrowKind::Ref
+    } else if segment.ident == "RefMut" {
+        BorrowKind::RefMut
+    } else {
+        return Err(error());
+    };
+
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return Err(error());
+    };
+    let value_type = args
+        .args
+        .iter()
+        .find_map(|arg| match arg {
+            GenericArgument::Type(value_type) => Some(value_type),
+            _ => None,
+        })
+        .ok_or_else(error)?;
+
+    Ok((borrow_kind, value_type))
+}
@@ -0,0 +1,100 @@
+use crate::Accessor;
+
+/// Set of keys that a unit of work borrows immutably and mutably.
+///
+/// This is an [`Accessor`] whose keys are determined at runtime.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_map::{AccessSet, Accessor};
+///
+/// let access_set = AccessSet::new().with_read('a').with_write('b');
+///
+/// assert_eq!(&['a'], access_set.reads());
+/// assert_eq!(&['b'], access_set.writes());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessSet<K> {
+    /// Keys of values that are borrowed immutably.
+    reads: Vec<K>,
+    /// Keys of values that are borrowed mutably.
+    writes: Vec<K>,
+}
+
+impl<K> AccessSet<K> {
+    /// Returns an empty `AccessSet`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a key whose value is borrowed immutably.
+    pub fn with_read(mut self, key: K) -> Self {
+        self.reads.push(key);
+        self
+    }
+
+    /// Adds a key whose value is borrowed mutably.
+    pub fn with_write(mut self, key: K) -> Self {
+        self.writes.push(key);
+        self
+    }
+}
+
+impl<K> Default for AccessSet<K> {
+    fn default() -> Self {
+        Self {
+            reads: Vec::new(),
+            writes: Vec::new(),
+        }
+    }
+}
+
+impl<K> Accessor<K> for AccessSet<K> {
+    fn reads(&self) -> &[K] {
+        &self.reads
+    }
+
+    fn writes(&self) -> &[K] {
+        &self.writes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AccessSet;
+    use crate::Accessor;
+
+    #[test]
+    fn reads_of_same_key_do_not_conflict() {
+        let a = AccessSet::new().with_read('a');
+        let b = AccessSet::new().with_read('a');
+
+        assert!(!a.conflicts_with(&b));
+    }
+
+    #[test]
+    fn write_and_read_of_same_key_conflict() {
+        let a = AccessSet::new().with_write('a');
+        let b = AccessSet::new().with_read('a');
+
+        assert!(a.conflicts_with(&b));
+        assert!(b.conflicts_with(&a));
+    }
+
+    #[test]
+    fn writes_of_same_key_conflict() {
+        let a = AccessSet::new().with_write('a');
+        let b = AccessSet::new().with_write('a');
+
+        assert!(a.conflicts_with(&b));
+    }
+
+    #[test]
+    fn writes_of_different_keys_do_not_conflict() {
+        let a = AccessSet::new().with_read('c').with_write('a');
+        let b = AccessSet::new().with_read('c').with_write('b');
+
+        assert!(!a.conflicts_with(&b));
+    }
+}
@@ -0,0 +1,48 @@
+/// Declares which keys a unit of work borrows from an [`RtMap`].
+///
+/// Schedulers can use this to determine which units of work may run at the
+/// same time: two accessors conflict when one of them writes to a key that
+/// the other reads from or writes to.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_map::{AccessSet, Accessor};
+///
+/// let physics = AccessSet::new().with_read('p').with_write('v');
+/// let render = AccessSet::new().with_read('p');
+/// let input = AccessSet::new().with_write('v');
+///
+/// assert!(!physics.conflicts_with(&render));
+/// assert!(physics.conflicts_with(&input));
+/// ```
+///
+/// [`RtMap`]: crate::RtMap
+pub trait Accessor<K> {
+    /// Returns the keys of values that are borrowed immutably.
+    fn reads(&self) -> &[K];
+
+    /// Returns the keys of values that are borrowed mutably.
+    fn writes(&self) -> &[K];
+
+    /// Returns whether this accessor and `other` cannot borrow their values
+    /// at the same time.
+    fn conflicts_with<A>(&self, other: &A) -> bool
+    where
+        A: Accessor<K> + ?Sized,
+        K: PartialEq,
+    {
+        writes_overlap(self.writes(), other.reads(), other.writes())
+            || writes_overlap(other.writes(), self.reads(), self.writes())
+    }
+}
+
+/// Returns whether any of `writes` is in `reads` or `other_writes`.
+fn writes_overlap<K>(writes: &[K], reads: &[K], other_writes: &[K]) -> bool
+where
+    K: PartialEq,
+{
+    writes
+        .iter()
+        .any(|key| reads.contains(key) || other_writes.contains(key))
+}
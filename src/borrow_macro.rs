@@ -0,0 +1,143 @@
+/// Borrows several values from an [`RtMap`] into local bindings.
+///
+/// Each binding is written as `name = key` to borrow the value immutably, or
+/// `mut name = key` to borrow it mutably. Values are borrowed in order with
+/// [`RtMap::try_borrow`] and [`RtMap::try_borrow_mut`], and the first
+/// [`BorrowFail`] is returned from the enclosing function with `?`, after
+/// releasing the values that were already borrowed.
+///
+/// A key may be borrowed immutably by several bindings.
+///
+/// # Panics
+///
+/// Panics if a key is borrowed mutably by one binding, and by any other
+/// binding, as that can never succeed. Keys are compared with `==` before
+/// any value is borrowed.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_map::{BorrowFail, RtMap, borrow};
+///
+/// fn step(rt_map: &RtMap<char, u32>) -> Result<(), BorrowFail> {
+///     borrow!(rt_map, mut p = 'p', v = 'v');
+///     *p += *v;
+///     Ok(())
+/// }
+///
+/// let mut rt_map = RtMap::new();
+/// rt_map.insert('p', 0);
+/// rt_map.insert('v', 2);
+///
+/// step(&rt_map).unwrap();
+/// assert_eq!(2, *rt_map.borrow(&'p'));
+///
+/// let _p = rt_map.borrow(&'p');
+/// assert_eq!(Err(BorrowFail::BorrowConflictMut), step(&rt_map));
+/// ```
+///
+/// [`BorrowFail`]: crate::BorrowFail
+/// [`RtMap`]: crate::RtMap
+/// [`RtMap::try_borrow`]: crate::RtMap::try_borrow
+/// [`RtMap::try_borrow_mut`]: crate::RtMap::try_borrow_mut
+#[macro_export]
+macro_rules! borrow {
+    ($rt_map:expr, $($bindings:tt)+) => {
+        $crate::borrow!(@munch $rt_map; []; $($bindings)+)
+    };
+    (@munch $rt_map:expr; [$($out:tt)*]; mut $name:ident = $key:expr $(, $($rest:tt)*)?) => {
+        $crate::borrow!(
+            @munch $rt_map;
+            [$($out)* ($name mutable [mut $name] try_borrow_mut $key)];
+            $($($rest)*)?
+        )
+    };
+    (@munch $rt_map:expr; [$($out:tt)*]; $name:ident = $key:expr $(, $($rest:tt)*)?) => {
+        $crate::borrow!(
+            @munch $rt_map;
+            [$($out)* ($name immutable [$name] try_borrow $key)];
+            $($($rest)*)?
+        )
+    };
+    (
+        @munch $rt_map:expr;
+        [$(($name:ident $kind:ident [$($binding:tt)+] $method:ident $key:expr))+];
+    ) => {
+        let rt_map = &$rt_map;
+        // Each key is held in a variable named after its binding, which is
+        // shadowed by the borrowed value.
+        let ($($name,)+) = ($(&$key,)+);
+        $crate::borrow!(@distinct $(($name $kind))+);
+        let ($($($binding)+,)+) = ($(rt_map.$method($name)?,)+);
+    };
+    (@distinct) => {};
+    (@distinct $first:tt $($rest:tt)*) => {
+        $crate::borrow!(@distinct_from $first; $($rest)*);
+        $crate::borrow!(@distinct $($rest)*);
+    };
+    (@distinct_from $first:tt;) => {};
+    (@distinct_from ($a:ident immutable); ($b:ident immutable) $($rest:tt)*) => {
+        $crate::borrow!(@distinct_from ($a immutable); $($rest)*);
+    };
+    (@distinct_from ($a:ident $a_kind:ident); ($b:ident $b_kind:ident) $($rest:tt)*) => {
+        if $a == $b {
+            panic!("Expected distinct keys, but `borrow!` was passed a key more than once.");
+        }
+        $crate::borrow!(@distinct_from ($a $a_kind); $($rest)*);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use rt_ref::BorrowFail;
+
+    use crate::RtMap;
+
+    fn sum_into_a(rt_map: &RtMap<char, u32>) -> Result<u32, BorrowFail> {
+        borrow!(rt_map, mut a = 'a', b = 'b', c = 'c');
+        *a += *b + *c;
+        Ok(*a)
+    }
+
+    #[test]
+    fn borrow_borrows_each_binding() {
+        let rt_map = RtMap::from([('a', 1), ('b', 2), ('c', 3)]);
+
+        assert_eq!(Ok(6), sum_into_a(&rt_map));
+    }
+
+    #[test]
+    fn borrow_releases_borrows_on_failure() {
+        let rt_map = RtMap::from([('a', 1), ('b', 2)]);
+
+        assert_eq!(Err(BorrowFail::ValueNotFound), sum_into_a(&rt_map));
+        assert!(rt_map.try_borrow_mut(&'a').is_ok());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected distinct keys, but `borrow!` was passed a key more than once."
+    )]
+    fn borrow_same_key_mutably_twice_panics() {
+        fn borrow_twice(rt_map: &RtMap<char, u32>) -> Result<(), BorrowFail> {
+            borrow!(rt_map, mut _a_0 = 'a', _b = 'b', mut _a_1 = 'a');
+            Ok(())
+        }
+
+        let rt_map = RtMap::from([('a', 1), ('b', 2)]);
+
+        let _ = borrow_twice(&rt_map);
+    }
+
+    #[test]
+    fn borrow_same_key_immutably_twice_borrows_both() {
+        fn sum_twice(rt_map: &RtMap<char, u32>) -> Result<u32, BorrowFail> {
+            borrow!(rt_map, a_0 = 'a', a_1 = 'a');
+            Ok(*a_0 + *a_1)
+        }
+
+        let rt_map = RtMap::from([('a', 1)]);
+
+        assert_eq!(Ok(2), sum_twice(&rt_map));
+    }
+}
@@ -0,0 +1,19 @@
+/// What to do with values that are being accessed mutably when serializing
+/// an [`RtMap`].
+///
+/// Passed to [`RtMap::serialize_with_policy`]. `Serialize` for `RtMap` uses
+/// [`BorrowPolicy::Error`].
+///
+/// There is no policy to wait for a value to be released, as `RtMap` does not
+/// track borrows in a way that can be waited on.
+///
+/// [`RtMap`]: crate::RtMap
+/// [`RtMap::serialize_with_policy`]: crate::RtMap::serialize_with_policy
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BorrowPolicy {
+    /// Fail serialization with an error.
+    #[default]
+    Error,
+    /// Leave the entry out of the serialized map.
+    Skip,
+}
@@ -0,0 +1,68 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rt_ref::BorrowFail;
+
+/// Counts of [`RtMap::try_borrow`] and [`RtMap::try_borrow_mut`] outcomes.
+///
+/// Retrieved with [`RtMap::borrow_stats`].
+///
+/// [`RtMap::borrow_stats`]: crate::RtMap::borrow_stats
+/// [`RtMap::try_borrow`]: crate::RtMap::try_borrow
+/// [`RtMap::try_borrow_mut`]: crate::RtMap::try_borrow_mut
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BorrowStats {
+    /// Number of successful borrows.
+    pub hits: u64,
+    /// Number of borrows of keys that are not in the map.
+    pub not_found: u64,
+    /// Number of borrows that conflicted with an existing borrow.
+    pub conflicts: u64,
+}
+
+impl BorrowStats {
+    /// Returns the fraction of borrows that succeeded, or `None` if there
+    /// have been no borrows.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.not_found + self.conflicts;
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+}
+
+/// Counters that record borrow outcomes.
+#[derive(Debug, Default)]
+pub(crate) struct BorrowCounters {
+    /// Number of successful borrows.
+    hits: AtomicU64,
+    /// Number of borrows of keys that are not in the map.
+    not_found: AtomicU64,
+    /// Number of borrows that conflicted with an existing borrow.
+    conflicts: AtomicU64,
+}
+
+impl BorrowCounters {
+    /// Records the outcome of a borrow.
+    pub(crate) fn record<T>(&self, result: &Result<T, BorrowFail>) {
+        let counter = match result {
+            Ok(_) => &self.hits,
+            Err(BorrowFail::ValueNotFound) => &self.not_found,
+            Err(BorrowFail::BorrowConflictImm | BorrowFail::BorrowConflictMut) => &self.conflicts,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the current counts.
+    pub(crate) fn stats(&self) -> BorrowStats {
+        BorrowStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            not_found: self.not_found.load(Ordering::Relaxed),
+            conflicts: self.conflicts.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Resets all counts to zero.
+    pub(crate) fn reset(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.not_found.store(0, Ordering::Relaxed);
+        self.conflicts.store(0, Ordering::Relaxed);
+    }
+}
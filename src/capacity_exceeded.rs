@@ -0,0 +1,26 @@
+/// A new key could not be inserted because the map is at its maximum number
+/// of entries.
+///
+/// Returned by [`RtMap::checked_insert`] and [`RtMap::checked_entry`] for
+/// maps built with [`RtMapBuilder::with_max_entries`].
+///
+/// [`RtMap::checked_entry`]: crate::RtMap::checked_entry
+/// [`RtMap::checked_insert`]: crate::RtMap::checked_insert
+/// [`RtMapBuilder::with_max_entries`]: crate::RtMapBuilder::with_max_entries
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapacityExceeded {
+    /// Maximum number of entries the map may hold.
+    max_entries: usize,
+}
+
+impl CapacityExceeded {
+    /// Returns a new `CapacityExceeded` error.
+    pub(crate) fn new(max_entries: usize) -> Self {
+        Self { max_entries }
+    }
+
+    /// Returns the maximum number of entries the map may hold.
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+}
@@ -0,0 +1,37 @@
+/// Changes that turn one [`RtMap`] into another.
+///
+/// Returned by [`RtMap::diff`]. Entries are in arbitrary order.
+///
+/// [`RtMap`]: crate::RtMap
+/// [`RtMap::diff`]: crate::RtMap::diff
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangeSet<K, V> {
+    /// Entries whose keys are only in the other map.
+    pub added: Vec<(K, V)>,
+    /// Keys that are only in this map.
+    pub removed: Vec<K>,
+    /// Entries whose keys are in both maps, with the other map's value.
+    pub modified: Vec<(K, V)>,
+}
+
+impl<K, V> ChangeSet<K, V> {
+    /// Returns an empty `ChangeSet`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if there are no changes.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+impl<K, V> Default for ChangeSet<K, V> {
+    fn default() -> Self {
+        Self {
+            added: Vec::new(),
+            removed: Vec::new(),
+            modified: Vec::new(),
+        }
+    }
+}
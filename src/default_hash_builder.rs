@@ -0,0 +1,8 @@
+/// Hash builder used by [`RtMap`] when no hasher is specified.
+///
+/// This is always [`RandomState`]. With the `"fast_hash"` feature, use
+/// `FastRtMap` for a map with a faster, non-HashDoS-resistant hasher.
+///
+/// [`RandomState`]: std::collections::hash_map::RandomState
+/// [`RtMap`]: crate::RtMap
+pub type DefaultHashBuilder = std::collections::hash_map::RandomState;
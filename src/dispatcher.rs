@@ -0,0 +1,228 @@
+use std::{
+    fmt,
+    hash::{BuildHasher, Hash},
+    thread,
+};
+
+use crate::{AccessSet, DefaultHashBuilder, RtMap, StagePlan};
+
+/// Runs systems that borrow from an [`RtMap`], in parallel where their
+/// declared accesses do not conflict.
+///
+/// Each system is registered with an [`AccessSet`] declaring the keys it
+/// reads and writes. Systems whose access sets conflict are run one after
+/// the other in the order they were added, while non-conflicting systems
+/// are run at the same time on scoped threads. See [`StagePlan`] for how
+/// systems are grouped.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_map::{AccessSet, Dispatcher, RtMap};
+///
+/// let mut rt_map = RtMap::new();
+/// rt_map.insert('p', 0);
+/// rt_map.insert('v', 1);
+///
+/// let mut dispatcher = Dispatcher::new()
+///     .with_system(AccessSet::new().with_write('v'), |rt_map| {
+///         *rt_map.borrow_mut(&'v') += 1;
+///     })
+///     .with_system(AccessSet::new().with_read('v').with_write('p'), |rt_map| {
+///         *rt_map.borrow_mut(&'p') += *rt_map.borrow(&'v');
+///     });
+///
+/// dispatcher.dispatch(&rt_map);
+///
+/// assert_eq!(2, *rt_map.borrow(&'p'));
+/// ```
+pub struct Dispatcher<'f, K, V, S = DefaultHashBuilder> {
+    /// Systems to run, in the order they were added.
+    systems: Vec<System<'f, K, V, S>>,
+}
+
+/// A unit of work and the keys it borrows.
+struct System<'f, K, V, S> {
+    /// Keys that the system borrows.
+    access_set: AccessSet<K>,
+    /// Logic to run.
+    run: SystemFn<'f, K, V, S>,
+}
+
+/// Logic of a system.
+type SystemFn<'f, K, V, S = DefaultHashBuilder> = Box<dyn FnMut(&RtMap<K, V, S>) + Send + 'f>;
+
+impl<'f, K, V, S> Dispatcher<'f, K, V, S>
+where
+    K: Hash + Eq + Send + Sync,
+    V: Send + Sync,
+    S: BuildHasher + Sync,
+{
+    /// Returns an empty `Dispatcher`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a system that borrows the keys in `access_set`.
+    ///
+    /// The system should only borrow the keys it declares, otherwise it may
+    /// encounter borrow conflicts with other systems that run at the same
+    /// time.
+    pub fn with_system<F>(mut self, access_set: AccessSet<K>, run: F) -> Self
+    where
+        F: FnMut(&RtMap<K, V, S>) + Send + 'f,
+    {
+        self.systems.push(System {
+            access_set,
+            run: Box::new(run),
+        });
+        self
+    }
+
+    /// Runs all systems once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any system panics.
+    pub fn dispatch(&mut self, rt_map: &RtMap<K, V, S>) {
+        let stage_plan = StagePlan::new(self.systems.iter().map(|system| &system.access_set));
+
+        stage_plan.stages().iter().for_each(|stage| {
+            let stage_systems = self
+                .systems
+                .iter_mut()
+                .enumerate()
+                .filter(|(index, _system)| stage.contains(index))
+                .map(|(_index, system)| system);
+
+            if stage.len() == 1 {
+                stage_systems.for_each(|system| (system.run)(rt_map));
+            } else {
+                thread::scope(|scope| {
+                    stage_systems.for_each(|system| {
+                        scope.spawn(|| (system.run)(rt_map));
+                    });
+                });
+            }
+        });
+    }
+}
+
+impl<K, V, S> Default for Dispatcher<'_, K, V, S> {
+    fn default() -> Self {
+        Self {
+            systems: Vec::new(),
+        }
+    }
+}
+
+impl<K, V, S> fmt::Debug for Dispatcher<'_, K, V, S>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dispatcher")
+            .field(
+                "systems",
+                &self
+                    .systems
+                    .iter()
+                    .map(|system| &system.access_set)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::BuildHasherDefault,
+        sync::{Arc, Barrier},
+    };
+
+    use super::Dispatcher;
+    use crate::{AccessSet, RtMap};
+
+    #[test]
+    fn dispatch_runs_conflicting_systems_in_order() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let mut dispatcher = Dispatcher::new()
+            .with_system(AccessSet::new().with_write('a'), |rt_map| {
+                *rt_map.borrow_mut(&'a') += 1;
+            })
+            .with_system(AccessSet::new().with_write('a'), |rt_map| {
+                *rt_map.borrow_mut(&'a') *= 10;
+            });
+
+        dispatcher.dispatch(&rt_map);
+
+        assert_eq!(20, *rt_map.borrow(&'a'));
+    }
+
+    #[test]
+    fn dispatch_runs_non_conflicting_systems_in_parallel() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        // Both systems must be running at the same time to pass the barrier.
+        let barrier = Arc::new(Barrier::new(2));
+        let barrier_a = barrier.clone();
+        let barrier_b = barrier;
+        let mut dispatcher = Dispatcher::new()
+            .with_system(AccessSet::new().with_write('a'), move |rt_map| {
+                let mut a = rt_map.borrow_mut(&'a');
+                barrier_a.wait();
+                *a += 1;
+            })
+            .with_system(AccessSet::new().with_write('b'), move |rt_map| {
+                let mut b = rt_map.borrow_mut(&'b');
+                barrier_b.wait();
+                *b += 1;
+            });
+
+        dispatcher.dispatch(&rt_map);
+
+        assert_eq!(2, *rt_map.borrow(&'a'));
+        assert_eq!(3, *rt_map.borrow(&'b'));
+    }
+
+    #[test]
+    fn dispatch_systems_can_be_dispatched_repeatedly() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 0);
+
+        let mut runs = 0;
+        let mut dispatcher =
+            Dispatcher::new().with_system(AccessSet::new().with_write('a'), |rt_map| {
+                runs += 1;
+                *rt_map.borrow_mut(&'a') += 1;
+            });
+
+        dispatcher.dispatch(&rt_map);
+        dispatcher.dispatch(&rt_map);
+        drop(dispatcher);
+
+        assert_eq!(2, runs);
+        assert_eq!(2, *rt_map.borrow(&'a'));
+    }
+
+    #[test]
+    fn dispatch_borrows_from_map_with_custom_hasher() {
+        let mut rt_map = RtMap::with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+        rt_map.insert('a', 1);
+
+        let mut dispatcher =
+            Dispatcher::new().with_system(AccessSet::new().with_write('a'), |rt_map| {
+                *rt_map.borrow_mut(&'a') += 1;
+            });
+
+        dispatcher.dispatch(&rt_map);
+
+        assert_eq!(2, *rt_map.borrow(&'a'));
+    }
+}
@@ -1,11 +1,13 @@
-use crate::{Cell, RefMut};
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Cell, DefaultHashBuilder, RefMut};
 
 #[derive(Debug)]
-pub struct Entry<'a, K, V> {
-    inner: Inner<'a, K, V>,
+pub struct Entry<'a, K, V, S = DefaultHashBuilder> {
+    inner: Inner<'a, K, V, S>,
 }
 
-pub type Inner<'a, K, V> = std::collections::hash_map::Entry<'a, K, Cell<V>>;
+pub type Inner<'a, K, V, S = DefaultHashBuilder> = hashbrown::hash_map::Entry<'a, K, Cell<V>, S>;
 
 /// An entry to a resource container.
 ///
@@ -24,9 +26,13 @@ pub type Inner<'a, K, V> = std::collections::hash_map::Entry<'a, K, Cell<V>>;
 /// let value = rt_map.entry(0).or_insert(Res(4));
 /// println!("{:?}", value.0 * 2);
 /// ```
-impl<'a, K, V> Entry<'a, K, V> {
+impl<'a, K, V, S> Entry<'a, K, V, S>
+where
+    K: Hash,
+    S: BuildHasher,
+{
     /// Create new entry.
-    pub fn new(inner: Inner<'a, K, V>) -> Self {
+    pub fn new(inner: Inner<'a, K, V, S>) -> Self {
         Self { inner }
     }
 
@@ -0,0 +1,82 @@
+use std::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use crate::{Cell, DefaultHashBuilder, RefMut};
+
+pub struct EntryRef<'a, 'b, K, Q, V, S = DefaultHashBuilder>
+where
+    Q: ?Sized,
+{
+    inner: Inner<'a, 'b, K, Q, V, S>,
+}
+
+pub type Inner<'a, 'b, K, Q, V, S = DefaultHashBuilder> =
+    hashbrown::hash_map::EntryRef<'a, 'b, K, Q, Cell<V>, S>;
+
+impl<K, Q, V, S> fmt::Debug for EntryRef<'_, '_, K, Q, V, S>
+where
+    K: Borrow<Q> + fmt::Debug,
+    Q: ?Sized + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EntryRef")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+/// An entry to a resource container, looked up by a borrowed key.
+///
+/// This is similar to [`Entry`], but the key is only converted into an owned
+/// `K` when the entry is vacant.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rt_map::RtMap;
+///
+/// let mut rt_map = RtMap::<String, u32>::default();
+///
+/// // Only allocates a `String` for the key the first time.
+/// *rt_map.entry_ref("a").or_insert(0) += 1;
+/// *rt_map.entry_ref("a").or_insert(0) += 1;
+///
+/// assert_eq!(2, *rt_map.borrow("a"));
+/// ```
+///
+/// [`Entry`]: crate::Entry
+impl<'a, 'b, K, Q, V, S> EntryRef<'a, 'b, K, Q, V, S>
+where
+    K: Hash + From<&'b Q>,
+    Q: ?Sized,
+    S: BuildHasher,
+{
+    /// Create new entry.
+    pub fn new(inner: Inner<'a, 'b, K, Q, V, S>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns this entry's value, inserts and returns `v` otherwise.
+    ///
+    /// Please note that you should use `or_insert_with` in case the creation of
+    /// the value is expensive.
+    pub fn or_insert(self, v: V) -> RefMut<'a, V> {
+        self.or_insert_with(move || v)
+    }
+
+    /// Returns this entry's value, inserts and returns the return value of `f`
+    /// otherwise.
+    pub fn or_insert_with<F>(self, f: F) -> RefMut<'a, V>
+    where
+        F: FnOnce() -> V,
+    {
+        let inner = self.inner.or_insert_with(move || Cell::new(f()));
+        let inner = inner.borrow_mut();
+
+        RefMut::new(inner)
+    }
+}
@@ -0,0 +1,39 @@
+use rustc_hash::FxBuildHasher;
+
+use crate::RtMap;
+
+/// [`RtMap`] that hashes keys with [`FxBuildHasher`].
+///
+/// This is faster than the default SipHash for small keys such as integers
+/// and `char`s, but is not resistant to HashDoS attacks, so it should not be
+/// used for keys that are controlled by untrusted input.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_map::FastRtMap;
+///
+/// let mut rt_map = FastRtMap::default();
+/// rt_map.insert('a', 1);
+///
+/// assert_eq!(1, *rt_map.borrow(&'a'));
+/// ```
+///
+/// [`FxBuildHasher`]: rustc_hash::FxBuildHasher
+pub type FastRtMap<K, V> = RtMap<K, V, FxBuildHasher>;
+
+#[cfg(test)]
+mod tests {
+    use super::FastRtMap;
+
+    #[test]
+    fn default_uses_fx_build_hasher() {
+        let mut rt_map = FastRtMap::default();
+        rt_map.insert(1u32, 'a');
+        rt_map.insert(2u32, 'b');
+
+        assert_eq!('a', *rt_map.borrow(&1));
+        assert_eq!('b', *rt_map.borrow(&2));
+        assert_eq!(2, rt_map.len());
+    }
+}
@@ -0,0 +1,160 @@
+use std::{
+    hash::{BuildHasher, Hash},
+    sync::Mutex,
+};
+
+use hashbrown::{Equivalent, HashMap};
+use rt_ref::Cell;
+
+#[cfg(feature = "stats")]
+use crate::borrow_stats::BorrowCounters;
+use crate::{DefaultHashBuilder, RtMap, ShrinkPolicy};
+
+/// An [`RtMap`] whose entries can no longer be inserted, removed, or
+/// mutated.
+///
+/// Returned by [`RtMap::freeze`]. Values are stored without their cells, so
+/// lookups hand out plain references and do not check or update any borrow
+/// flags. This suits load-then-read workloads such as configuration and
+/// asset tables.
+///
+/// Use [`thaw`] to get the [`RtMap`] back.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_map::RtMap;
+///
+/// let mut rt_map = RtMap::new();
+/// rt_map.insert('a', 1);
+///
+/// let frozen_rt_map = rt_map.freeze();
+/// assert_eq!(Some(&1), frozen_rt_map.get(&'a'));
+///
+/// let mut rt_map = frozen_rt_map.thaw();
+/// *rt_map.borrow_mut(&'a') += 1;
+/// assert_eq!(2, *rt_map.borrow(&'a'));
+/// ```
+///
+/// [`RtMap::freeze`]: crate::RtMap::freeze
+/// [`thaw`]: Self::thaw
+#[derive(Debug)]
+pub struct FrozenRtMap<K, V, S = DefaultHashBuilder> {
+    /// The map's entries, moved out of their cells.
+    pub(crate) inner: HashMap<K, V, S>,
+    /// When to release unused capacity after removals, restored on thaw.
+    pub(crate) shrink_policy: ShrinkPolicy,
+    /// Maximum number of entries the map may hold, restored on thaw.
+    pub(crate) max_entries: Option<usize>,
+}
+
+impl<K, V, S> FrozenRtMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    /// Returns a reference to the value for `k`.
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.inner.get(k)
+    }
+
+    /// Returns the stored key and a reference to the value for `k`.
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.inner.get_key_value(k)
+    }
+
+    /// Returns `true` if the map contains a value for `k`.
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.inner.contains_key(k)
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns an iterator over the map's entries, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.inner.iter()
+    }
+
+    /// Returns an iterator over the map's keys, in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.inner.keys()
+    }
+
+    /// Returns an iterator over the map's values, in arbitrary order.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.inner.values()
+    }
+
+    /// Returns the [`RtMap`], so that its entries can be mutated again.
+    ///
+    /// The map's configuration, such as its [`ShrinkPolicy`] and maximum
+    /// number of entries, is kept. With the `"stats"` feature, the borrow
+    /// counters start again from zero.
+    pub fn thaw(self) -> RtMap<K, V, S>
+    where
+        S: Clone,
+    {
+        let Self {
+            inner: frozen_inner,
+            shrink_policy,
+            max_entries,
+        } = self;
+
+        let mut inner =
+            HashMap::with_capacity_and_hasher(frozen_inner.len(), frozen_inner.hasher().clone());
+        frozen_inner.into_iter().for_each(|(k, v)| {
+            inner.insert(k, Cell::new(v));
+        });
+
+        RtMap {
+            inner,
+            shrink_policy,
+            max_entries,
+            deferred_removals: Mutex::default(),
+            #[cfg(feature = "stats")]
+            borrow_counters: BorrowCounters::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RtMap;
+
+    #[test]
+    fn freeze_then_thaw_keeps_entries_and_configuration() {
+        let mut rt_map = RtMap::<String, u32>::builder().with_max_entries(2).build();
+        rt_map.insert(String::from("a"), 1);
+        rt_map.insert(String::from("b"), 2);
+
+        let frozen_rt_map = rt_map.freeze();
+        let a = frozen_rt_map.get("a").unwrap();
+        let b = frozen_rt_map.get("b").unwrap();
+        assert_eq!(3, a + b);
+        assert_eq!(None, frozen_rt_map.get("c"));
+        assert_eq!(2, frozen_rt_map.len());
+
+        let mut rt_map = frozen_rt_map.thaw();
+        *rt_map.borrow_mut("a") += 10;
+        assert_eq!(11, *rt_map.borrow("a"));
+        assert_eq!(Some(2), rt_map.max_entries());
+        assert!(rt_map.checked_insert(String::from("c"), 3).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use std::hash::{BuildHasher, Hash};
+
+use hashbrown::{Equivalent, HashMap};
+
+use crate::DefaultHashBuilder;
+
+/// Read-only view of an [`RtMap`] that hands out plain references.
+///
+/// Returned by [`RtMap::snapshot`]. The view holds the map mutably borrowed,
+/// so no [`Ref`] or [`RefMut`] can exist while it is alive, and lookups do
+/// not need to check or update any borrow flags.
+///
+/// [`Ref`]: crate::Ref
+/// [`RefMut`]: crate::RefMut
+/// [`RtMap`]: crate::RtMap
+/// [`RtMap::snapshot`]: crate::RtMap::snapshot
+#[derive(Debug)]
+pub struct FrozenView<'map, K, V, S = DefaultHashBuilder> {
+    /// References to the map's keys and values.
+    entries: HashMap<&'map K, &'map V, S>,
+}
+
+impl<'map, K, V, S> FrozenView<'map, K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    /// Returns a new `FrozenView`.
+    pub(crate) fn new(entries: HashMap<&'map K, &'map V, S>) -> Self {
+        Self { entries }
+    }
+
+    /// Returns a reference to the value for `k`.
+    pub fn get<Q>(&self, k: &Q) -> Option<&'map V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.get_key_value(k).map(|(_k, v)| v)
+    }
+
+    /// Returns the stored key and a reference to the value for `k`.
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&'map K, &'map V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let hash = self.entries.hasher().hash_one(k);
+        self.entries
+            .raw_entry()
+            .from_hash(hash, |stored_key| k.equivalent(*stored_key))
+            .map(|(k, v)| (*k, *v))
+    }
+
+    /// Returns `true` if the map contains a value for `k`.
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.get_key_value(k).is_some()
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the map's entries, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'map K, &'map V)> + '_ {
+        self.entries.iter().map(|(k, v)| (*k, *v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RtMap;
+
+    #[test]
+    fn get_returns_references_that_outlive_lookups() {
+        let mut rt_map = RtMap::<String, u32>::new();
+        rt_map.insert(String::from("a"), 1);
+        rt_map.insert(String::from("b"), 2);
+
+        let frozen_view = rt_map.snapshot();
+        let a = frozen_view.get("a").unwrap();
+        let b = frozen_view.get("b").unwrap();
+
+        assert_eq!(3, a + b);
+        assert_eq!(None, frozen_view.get("c"));
+        assert_eq!(
+            Some((&String::from("a"), &1)),
+            frozen_view.get_key_value("a")
+        );
+        assert_eq!(2, frozen_view.len());
+        assert_eq!(2, frozen_view.iter().count());
+    }
+}
@@ -0,0 +1,39 @@
+use std::{fmt, iter::FusedIterator};
+
+use rt_ref::Cell;
+
+/// An owning iterator over the entries of an [`RtMap`].
+///
+/// Returned by [`RtMap::into_iter`].
+///
+/// [`RtMap`]: crate::RtMap
+/// [`RtMap::into_iter`]: crate::RtMap::into_iter
+pub struct IntoIter<K, V>(pub(crate) hashbrown::hash_map::IntoIter<K, Cell<V>>);
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, cell)| (k, cell.into_inner()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+impl<K, V> fmt::Debug for IntoIter<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntoIter")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
@@ -0,0 +1,66 @@
+use std::{error::Error, fmt};
+
+use rt_ref::BorrowFail;
+
+/// Failed to borrow a value, along with the key that was borrowed.
+///
+/// Returned by [`RtMap::try_borrow_keyed`] and
+/// [`RtMap::try_borrow_mut_keyed`]. Unlike [`BorrowFail`], this implements
+/// [`Error`], and its message includes the key.
+///
+/// [`RtMap::try_borrow_keyed`]: crate::RtMap::try_borrow_keyed
+/// [`RtMap::try_borrow_mut_keyed`]: crate::RtMap::try_borrow_mut_keyed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyedBorrowFail<K> {
+    /// The key that was borrowed.
+    pub key: K,
+    /// Why the value could not be borrowed.
+    pub borrow_fail: BorrowFail,
+}
+
+impl<K> fmt::Display for KeyedBorrowFail<K>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = &self.key;
+        match self.borrow_fail {
+            BorrowFail::ValueNotFound => {
+                write!(
+                    f,
+                    "Failed to borrow the value for `{key:?}`, as it does not exist."
+                )
+            }
+            BorrowFail::BorrowConflictImm => write!(
+                f,
+                "Failed to borrow the value for `{key:?}`, as it is already borrowed mutably."
+            ),
+            BorrowFail::BorrowConflictMut => write!(
+                f,
+                "Failed to mutably borrow the value for `{key:?}`, as it is already borrowed."
+            ),
+        }
+    }
+}
+
+impl<K> Error for KeyedBorrowFail<K> where K: fmt::Debug {}
+
+#[cfg(test)]
+mod tests {
+    use rt_ref::BorrowFail;
+
+    use super::KeyedBorrowFail;
+
+    #[test]
+    fn display_includes_key() {
+        let keyed_borrow_fail = KeyedBorrowFail {
+            key: 'a',
+            borrow_fail: BorrowFail::BorrowConflictMut,
+        };
+
+        assert_eq!(
+            "Failed to mutably borrow the value for `'a'`, as it is already borrowed.",
+            keyed_borrow_fail.to_string()
+        );
+    }
+}
@@ -12,7 +12,15 @@
 //!
 //! ```toml
 //! rt_map = "0.5.4" # or
-//! rt_map = { version = "0.5.4", features = ["unsafe_debug"] }
+//! rt_map = { version = "0.5.4", features = [
+//!     "derive",
+//!     "fast_hash",
+//!     "rayon",
+//!     "rt_index_map",
+//!     "serde",
+//!     "stats",
+//!     "unsafe_debug",
+//! ] }
 //! ```
 //!
 //! In code:
@@ -61,6 +69,57 @@
 //!
 //! ### Features
 //!
+//! #### `"derive"`
+//!
+//! Enables `#[derive(RtAccess)]`, which borrows a struct of `Ref` and `RefMut`
+//! fields from an `RtMap` in one call.
+//!
+//! ```rust,ignore
+//! use rt_map::{Ref, RefMut, RtAccess, RtMap};
+//!
+//! #[derive(RtAccess)]
+//! #[rt_access(key_type = char)]
+//! struct Data<'a> {
+//!     #[rt_access(key = 'a')]
+//!     a: Ref<'a, u32>,
+//!     #[rt_access(key = 'b')]
+//!     b: RefMut<'a, u32>,
+//! }
+//!
+//! let data = Data::fetch(&rt_map)?;
+//! ```
+//!
+//! #### `"fast_hash"`
+//!
+//! Enables the `FastRtMap` type, an `RtMap` that uses `FxBuildHasher` from
+//! [`rustc-hash`], which is faster than SipHash for small keys, but is not
+//! resistant to HashDoS attacks. The default hasher of `RtMap` is unchanged.
+//!
+//! #### `"rayon"`
+//!
+//! Enables `RtMap::par_drain`, which removes every entry as a [`rayon`]
+//! parallel iterator of owned key-value pairs.
+//!
+//! #### `"rt_index_map"`
+//!
+//! Enables the `RtIndexMap` type, which stores values contiguously in
+//! insertion order, so iterating over every entry is cache friendly.
+//!
+//! #### `"serde"`
+//!
+//! Implements `Serialize` and `Deserialize` for `RtMap`. Serializing a map
+//! fails if a value is being accessed mutably, as it cannot be read. Use
+//! `RtMap::serialize_with_policy` with `BorrowPolicy::Skip` to leave those
+//! entries out instead.
+//! Deserialized maps use the default configuration, such as no maximum
+//! number of entries.
+//!
+//! #### `"stats"`
+//!
+//! Counts successful, not found, and conflicting `try_borrow` and
+//! `try_borrow_mut` calls, retrievable with `RtMap::borrow_stats`, and
+//! included in `RtMap::stats`.
+//!
 //! #### `"unsafe_debug"`
 //!
 //! Enables the [`"unsafe_debug"`] feature of [`rt_ref`].
@@ -75,13 +134,162 @@
 //! [`anymap`]: https://github.com/chris-morgan/anymap
 //! [`resman`]: https://github.com/azriel91/resman
 //! [`rt_vec`]: https://crates.io/crates/rt_vec
+//! [`rayon`]: https://crates.io/crates/rayon
+//! [`rustc-hash`]: https://crates.io/crates/rustc-hash
 //! [`shred`]: https://github.com/amethyst/shred
 //! [`"unsafe_debug"`]: https://github.com/azriel91/rt_ref#unsafe_debug
 
+#![forbid(unsafe_code)]
+
 // Re-exports
+pub use hashbrown::{self, Equivalent};
 pub use rt_ref::{BorrowFail, Cell, CellRef, CellRefMut, Ref, RefMut};
 
-pub use crate::{entry::Entry, rt_map::RtMap};
+pub use crate::{
+    access_set::AccessSet, accessor::Accessor, capacity_exceeded::CapacityExceeded,
+    change_set::ChangeSet, default_hash_builder::DefaultHashBuilder, dispatcher::Dispatcher,
+    entry::Entry, entry_ref::EntryRef, frozen_rt_map::FrozenRtMap, frozen_view::FrozenView,
+    into_iter::IntoIter, keyed_borrow_fail::KeyedBorrowFail, occupied_fail::OccupiedFail,
+    project::Project, projection_fail::ProjectionFail, raw_entry_builder::RawEntryBuilder,
+    raw_entry_builder_mut::RawEntryBuilderMut, raw_entry_mut::RawEntryMut,
+    replace_key_fail::ReplaceKeyFail, retain_action::RetainAction, rt_access::RtAccess,
+    rt_map::RtMap, rt_map_builder::RtMapBuilder, rt_map_stats::RtMapStats, scope::Scope,
+    shrink_policy::ShrinkPolicy, stage_plan::StagePlan, transaction::Transaction,
+};
+
+#[cfg(feature = "fast_hash")]
+pub use crate::fast_rt_map::FastRtMap;
+#[cfg(feature = "rt_index_map")]
+pub use crate::rt_index_map::RtIndexMap;
+#[cfg(feature = "fast_hash")]
+pub use rustc_hash::{FxBuildHasher, FxHasher};
+
+#[cfg(feature = "serde")]
+pub use crate::borrow_policy::BorrowPolicy;
+#[cfg(feature = "stats")]
+pub use crate::borrow_stats::BorrowStats;
+
+#[cfg(feature = "derive")]
+pub use rt_map_derive::RtAccess;
+
+/// Panics because a key does not exist.
+///
+/// Only the key's type is included in the message, so that keys are not
+/// required to implement `Debug`. Pass `debug` to include the key itself.
+macro_rules! borrow_panic {
+    ($key:ident) => {
+        panic!(
+            "Expected to borrow a value with a `{key_type}` key, but the key does not exist.",
+            key_type = std::any::type_name_of_val($key),
+        )
+    };
+    ($key:ident, debug) => {
+        panic!(
+            "Expected to borrow the value for `{key:?}`, but the key does not exist.",
+            key = $key,
+        )
+    };
+}
+
+/// Panics because a value is already borrowed in a conflicting way.
+///
+/// Pass `debug` to include the key in the message, instead of its type.
+macro_rules! borrow_conflict_panic {
+    ($key:ident, $cell:ident, $borrow:literal) => {
+        panic!(
+            "Expected to {borrow} a value with a `{key_type}` key, \
+            but it was already borrowed {borrowed}.",
+            borrow = $borrow,
+            key_type = std::any::type_name_of_val($key),
+            borrowed = borrowed_description!($cell),
+        )
+    };
+    ($key:ident, $cell:ident, $borrow:literal, debug) => {
+        panic!(
+            "Expected to {borrow} the value for `{key:?}`, but it was already borrowed {borrowed}.",
+            borrow = $borrow,
+            key = $key,
+            borrowed = borrowed_description!($cell),
+        )
+    };
+}
+
+/// Returns whether a value that could not be borrowed is borrowed mutably or
+/// immutably.
+macro_rules! borrowed_description {
+    ($cell:ident) => {
+        // This briefly borrows the value, but only when about to panic.
+        match $cell.try_borrow() {
+            Ok(_) => "immutably",
+            Err(_) => "mutably",
+        }
+    };
+}
+
+/// Panics because a value is not of the type it was borrowed as.
+macro_rules! projection_panic {
+    ($key:ident, $ty:ty) => {
+        panic!(
+            "Expected to borrow a value with a `{key_type}` key as `{ty}`, \
+            but it is a different type.",
+            key_type = std::any::type_name_of_val($key),
+            ty = std::any::type_name::<$ty>(),
+        )
+    };
+}
+
+/// Panics because a new key would exceed the map's maximum number of entries.
+macro_rules! capacity_panic {
+    ($capacity_exceeded:ident) => {
+        panic!(
+            "Expected to insert a new key, but the map is at its maximum of {max_entries} entries.",
+            max_entries = $capacity_exceeded.max_entries(),
+        )
+    };
+}
+
+pub mod prelude;
 
+mod access_set;
+mod accessor;
+mod borrow_macro;
+#[cfg(feature = "serde")]
+mod borrow_policy;
+#[cfg(feature = "stats")]
+mod borrow_stats;
+mod capacity_exceeded;
+mod change_set;
+mod default_hash_builder;
+mod dispatcher;
 mod entry;
+mod entry_ref;
+#[cfg(feature = "fast_hash")]
+mod fast_rt_map;
+mod frozen_rt_map;
+mod frozen_view;
+mod into_iter;
+mod keyed_borrow_fail;
+mod occupied_fail;
+mod project;
+mod projection_fail;
+mod raw_entry_builder;
+mod raw_entry_builder_mut;
+mod raw_entry_mut;
+mod replace_key_fail;
+mod retain_action;
+mod rt_access;
+#[cfg(feature = "rt_index_map")]
+mod rt_index_map;
+mod rt_key;
 mod rt_map;
+mod rt_map_builder;
+mod rt_map_macro;
+#[cfg(feature = "rayon")]
+mod rt_map_rayon;
+#[cfg(feature = "serde")]
+mod rt_map_serde;
+mod rt_map_stats;
+mod scope;
+mod shrink_policy;
+mod stage_plan;
+mod transaction;
@@ -0,0 +1,13 @@
+/// A value was not inserted because its key is already in the map.
+///
+/// Returned by [`RtMap::try_insert`], and holds the key and value that were
+/// not inserted.
+///
+/// [`RtMap::try_insert`]: crate::RtMap::try_insert
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OccupiedFail<K, V> {
+    /// The key that is already in the map.
+    pub key: K,
+    /// The value that was not inserted.
+    pub value: V,
+}
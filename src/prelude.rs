@@ -0,0 +1,25 @@
+//! Commonly used types and traits, for glob importing.
+//!
+//! ```rust
+//! use rt_map::prelude::*;
+//!
+//! let mut rt_map = RtMap::new();
+//! rt_map.insert('a', 1);
+//!
+//! let a: Ref<'_, u32> = rt_map.borrow(&'a');
+//! assert_eq!(1, *a);
+//! ```
+
+#[cfg(feature = "serde")]
+pub use crate::BorrowPolicy;
+#[cfg(feature = "stats")]
+pub use crate::BorrowStats;
+#[cfg(feature = "fast_hash")]
+pub use crate::FastRtMap;
+#[cfg(feature = "rt_index_map")]
+pub use crate::RtIndexMap;
+pub use crate::{
+    AccessSet, Accessor, BorrowFail, CapacityExceeded, ChangeSet, Entry, EntryRef, Equivalent,
+    KeyedBorrowFail, OccupiedFail, Project, ProjectionFail, Ref, RefMut, ReplaceKeyFail,
+    RetainAction, RtAccess, RtMap, RtMapBuilder, RtMapStats, Scope, ShrinkPolicy, Transaction,
+};
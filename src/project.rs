@@ -0,0 +1,16 @@
+/// Narrows a value to a part of it, such as an enum variant's payload.
+///
+/// Implementing this for a value type allows its guards to be narrowed with
+/// [`RtMap::borrow_variant`] and [`RtMap::borrow_mut_variant`].
+///
+/// Both methods must agree on whether the value projects to a `T`.
+///
+/// [`RtMap::borrow_variant`]: crate::RtMap::borrow_variant
+/// [`RtMap::borrow_mut_variant`]: crate::RtMap::borrow_mut_variant
+pub trait Project<T> {
+    /// Returns a reference to the projected part, if present.
+    fn project(&self) -> Option<&T>;
+
+    /// Returns a mutable reference to the projected part, if present.
+    fn project_mut(&mut self) -> Option<&mut T>;
+}
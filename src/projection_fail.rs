@@ -0,0 +1,22 @@
+use rt_ref::BorrowFail;
+
+/// Failed to borrow a value as a narrower type.
+///
+/// Returned when borrowing a value through a downcast or projection, for
+/// example [`RtMap::try_borrow_downcast`] and [`RtMap::try_borrow_variant`].
+///
+/// [`RtMap::try_borrow_downcast`]: crate::RtMap::try_borrow_downcast
+/// [`RtMap::try_borrow_variant`]: crate::RtMap::try_borrow_variant
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectionFail {
+    /// The value could not be borrowed.
+    Borrow(BorrowFail),
+    /// The value exists, but is not of the requested type or variant.
+    Mismatch,
+}
+
+impl From<BorrowFail> for ProjectionFail {
+    fn from(borrow_fail: BorrowFail) -> Self {
+        Self::Borrow(borrow_fail)
+    }
+}
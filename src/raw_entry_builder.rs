@@ -0,0 +1,113 @@
+use std::hash::{BuildHasher, Hash};
+
+use hashbrown::Equivalent;
+use rt_ref::{BorrowFail, Cell, Ref};
+
+use crate::{DefaultHashBuilder, RtMap};
+
+/// Looks up an entry by hash, without needing an owned or borrowed key.
+///
+/// Returned by [`RtMap::raw_entry`]. Lookups return the stored key and an
+/// immutable borrow of the value, like [`RtMap::try_borrow`]. Use
+/// [`RtMap::get_raw`] for access to the value's cell.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::hash::BuildHasher;
+///
+/// use rt_map::RtMap;
+///
+/// let mut rt_map = RtMap::new();
+/// rt_map.insert(String::from("a"), 1);
+///
+/// let hash = rt_map.hasher().hash_one("a");
+/// let (k, a) = rt_map.raw_entry().from_hash(hash, |k| k == "a").unwrap();
+///
+/// assert_eq!("a", k);
+/// assert_eq!(1, *a);
+/// ```
+///
+/// [`RtMap::get_raw`]: crate::RtMap::get_raw
+/// [`RtMap::raw_entry`]: crate::RtMap::raw_entry
+/// [`RtMap::try_borrow`]: crate::RtMap::try_borrow
+#[derive(Debug)]
+pub struct RawEntryBuilder<'a, K, V, S = DefaultHashBuilder> {
+    rt_map: &'a RtMap<K, V, S>,
+}
+
+impl<'a, K, V, S> RawEntryBuilder<'a, K, V, S> {
+    /// Create new raw entry builder.
+    pub(crate) fn new(rt_map: &'a RtMap<K, V, S>) -> Self {
+        Self { rt_map }
+    }
+
+    /// Returns the stored key and a reference to the value for `k`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`BorrowFail`] if the value does not exist, or is
+    /// borrowed mutably.
+    pub fn from_key<Q>(self, k: &Q) -> Result<(&'a K, Ref<'a, V>), BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+        S: BuildHasher,
+    {
+        self.borrow(self.rt_map.inner.raw_entry().from_key(k))
+    }
+
+    /// Returns the stored key and a reference to the value for `k`, using
+    /// `hash` as the key's hash.
+    ///
+    /// `hash` must be the hash of `k` computed with the map's hasher.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`BorrowFail`] if the value does not exist, or is
+    /// borrowed mutably.
+    pub fn from_key_hashed_nocheck<Q>(
+        self,
+        hash: u64,
+        k: &Q,
+    ) -> Result<(&'a K, Ref<'a, V>), BorrowFail>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        self.borrow(
+            self.rt_map
+                .inner
+                .raw_entry()
+                .from_key_hashed_nocheck(hash, k),
+        )
+    }
+
+    /// Returns the stored key and a reference to the value for the entry with
+    /// the given `hash` whose key matches `is_match`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`BorrowFail`] if the value does not exist, or is
+    /// borrowed mutably.
+    pub fn from_hash<F>(self, hash: u64, is_match: F) -> Result<(&'a K, Ref<'a, V>), BorrowFail>
+    where
+        F: FnMut(&K) -> bool,
+    {
+        self.borrow(self.rt_map.inner.raw_entry().from_hash(hash, is_match))
+    }
+
+    /// Borrows the value of a looked up entry, recording the outcome like
+    /// [`RtMap::try_borrow`].
+    fn borrow(
+        &self,
+        entry: Option<(&'a K, &'a Cell<V>)>,
+    ) -> Result<(&'a K, Ref<'a, V>), BorrowFail> {
+        let result = entry
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(|(k, cell)| cell.try_borrow().map(|v| (k, Ref::new(v))));
+
+        #[cfg(feature = "stats")]
+        self.rt_map.borrow_counters.record(&result);
+
+        result
+    }
+}
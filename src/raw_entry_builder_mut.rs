@@ -0,0 +1,55 @@
+use std::hash::{BuildHasher, Hash};
+
+use hashbrown::Equivalent;
+
+use crate::{Cell, DefaultHashBuilder, RawEntryMut};
+
+pub type Inner<'a, K, V, S = DefaultHashBuilder> =
+    hashbrown::hash_map::RawEntryBuilderMut<'a, K, Cell<V>, S>;
+
+/// Looks up an entry by hash for in-place manipulation, without needing an
+/// owned key.
+///
+/// Returned by [`RtMap::raw_entry_mut`].
+///
+/// [`RtMap::raw_entry_mut`]: crate::RtMap::raw_entry_mut
+#[derive(Debug)]
+pub struct RawEntryBuilderMut<'a, K, V, S = DefaultHashBuilder> {
+    inner: Inner<'a, K, V, S>,
+    /// Maximum number of entries, if the map is already at that limit.
+    full_at: Option<usize>,
+}
+
+impl<'a, K, V, S> RawEntryBuilderMut<'a, K, V, S> {
+    /// Create new raw entry builder.
+    pub(crate) fn new(inner: Inner<'a, K, V, S>, full_at: Option<usize>) -> Self {
+        Self { inner, full_at }
+    }
+
+    /// Returns the entry for `k`.
+    pub fn from_key<Q>(self, k: &Q) -> RawEntryMut<'a, K, V, S>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+        S: BuildHasher,
+    {
+        RawEntryMut::new(self.inner.from_key(k), self.full_at)
+    }
+
+    /// Returns the entry for `k`, using `hash` as the key's hash.
+    ///
+    /// `hash` must be the hash of `k` computed with the map's hasher.
+    pub fn from_key_hashed_nocheck<Q>(self, hash: u64, k: &Q) -> RawEntryMut<'a, K, V, S>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        RawEntryMut::new(self.inner.from_key_hashed_nocheck(hash, k), self.full_at)
+    }
+
+    /// Returns the entry with the given `hash` whose key matches `is_match`.
+    pub fn from_hash<F>(self, hash: u64, is_match: F) -> RawEntryMut<'a, K, V, S>
+    where
+        F: FnMut(&K) -> bool,
+    {
+        RawEntryMut::new(self.inner.from_hash(hash, is_match), self.full_at)
+    }
+}
@@ -0,0 +1,94 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::{CapacityExceeded, Cell, DefaultHashBuilder, RefMut};
+
+pub type Inner<'a, K, V, S = DefaultHashBuilder> =
+    hashbrown::hash_map::RawEntryMut<'a, K, Cell<V>, S>;
+
+/// An entry to a resource container, looked up by hash.
+///
+/// This is similar to [`Entry`], but the owned key is only needed when the
+/// entry is vacant.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::hash::BuildHasher;
+///
+/// use rt_map::RtMap;
+///
+/// let mut rt_map = RtMap::<String, u32>::new();
+///
+/// let hash = rt_map.hasher().hash_one("a");
+/// let (_k, mut a) = rt_map
+///     .raw_entry_mut()
+///     .from_hash(hash, |k| k == "a")
+///     .or_insert_with(|| (String::from("a"), 0));
+/// *a += 1;
+/// drop(a);
+///
+/// assert_eq!(1, *rt_map.borrow("a"));
+/// ```
+///
+/// [`Entry`]: crate::Entry
+#[derive(Debug)]
+pub struct RawEntryMut<'a, K, V, S = DefaultHashBuilder> {
+    inner: Inner<'a, K, V, S>,
+    /// Maximum number of entries, if the map is already at that limit.
+    full_at: Option<usize>,
+}
+
+impl<'a, K, V, S> RawEntryMut<'a, K, V, S> {
+    /// Create new raw entry.
+    pub(crate) fn new(inner: Inner<'a, K, V, S>, full_at: Option<usize>) -> Self {
+        Self { inner, full_at }
+    }
+}
+
+impl<'a, K, V, S> RawEntryMut<'a, K, V, S>
+where
+    K: Hash,
+    S: BuildHasher,
+{
+    /// Returns this entry's key and value, inserts and returns `k` and `v`
+    /// otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry is vacant and the map is at its [maximum number of
+    /// entries].
+    ///
+    /// [maximum number of entries]: crate::RtMapBuilder::with_max_entries
+    pub fn or_insert(self, k: K, v: V) -> (&'a K, RefMut<'a, V>) {
+        self.or_insert_with(move || (k, v))
+    }
+
+    /// Returns this entry's key and value, inserts and returns the return
+    /// value of `f` otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry is vacant and the map is at its [maximum number of
+    /// entries].
+    ///
+    /// [maximum number of entries]: crate::RtMapBuilder::with_max_entries
+    pub fn or_insert_with<F>(self, f: F) -> (&'a K, RefMut<'a, V>)
+    where
+        F: FnOnce() -> (K, V),
+    {
+        let (k, cell) = match self.inner {
+            Inner::Occupied(occupied) => occupied.into_key_value(),
+            Inner::Vacant(vacant) => {
+                if let Some(max_entries) = self.full_at {
+                    let capacity_exceeded = CapacityExceeded::new(max_entries);
+                    capacity_panic!(capacity_exceeded);
+                }
+
+                let (k, v) = f();
+                vacant.insert(k, Cell::new(v))
+            }
+        };
+
+        (k, RefMut::new(cell.borrow_mut()))
+    }
+}
@@ -0,0 +1,12 @@
+/// Failed to move a value to a different key.
+///
+/// Returned by [`RtMap::replace_key`].
+///
+/// [`RtMap::replace_key`]: crate::RtMap::replace_key
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplaceKeyFail {
+    /// The key to move the value from is not in the map.
+    KeyNotFound,
+    /// The key to move the value to is already in the map.
+    KeyExists,
+}
@@ -0,0 +1,13 @@
+/// What to do with the current entry when walking a map with
+/// [`RtMap::retain_until`].
+///
+/// [`RtMap::retain_until`]: crate::RtMap::retain_until
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RetainAction {
+    /// Keep the entry and move on to the next one.
+    Keep,
+    /// Remove the entry and move on to the next one.
+    Remove,
+    /// Keep the entry and every entry after it.
+    Stop,
+}
@@ -0,0 +1,51 @@
+use rt_ref::BorrowFail;
+
+use crate::{DefaultHashBuilder, RtMap};
+
+/// Borrows a set of values from an [`RtMap`] in one call.
+///
+/// This is typically implemented for a struct of [`Ref`] and [`RefMut`]
+/// fields using `#[derive(RtAccess)]`, which requires the `"derive"` feature.
+/// Either all values are borrowed, or the first [`BorrowFail`] is returned
+/// and no values remain borrowed.
+///
+/// The derived implementation is generic over the map's hasher `S`, so it
+/// can fetch from maps built with any [`BuildHasher`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_map::{BorrowFail, Ref, RefMut, RtAccess, RtMap};
+///
+/// struct Data<'a> {
+///     a: Ref<'a, u32>,
+///     b: RefMut<'a, u32>,
+/// }
+///
+/// impl<'a> RtAccess<'a, char, u32> for Data<'a> {
+///     fn fetch(rt_map: &'a RtMap<char, u32>) -> Result<Self, BorrowFail> {
+///         Ok(Self {
+///             a: rt_map.try_borrow(&'a')?,
+///             b: rt_map.try_borrow_mut(&'b')?,
+///         })
+///     }
+/// }
+///
+/// let mut rt_map = RtMap::new();
+/// rt_map.insert('a', 1);
+/// rt_map.insert('b', 2);
+///
+/// let mut data = Data::fetch(&rt_map).unwrap();
+/// *data.b += *data.a;
+/// drop(data);
+///
+/// assert_eq!(3, *rt_map.borrow(&'b'));
+/// ```
+///
+/// [`BuildHasher`]: std::hash::BuildHasher
+/// [`Ref`]: crate::Ref
+/// [`RefMut`]: crate::RefMut
+pub trait RtAccess<'a, K, V, S = DefaultHashBuilder>: Sized {
+    /// Borrows each value from the map.
+    fn fetch(rt_map: &'a RtMap<K, V, S>) -> Result<Self, BorrowFail>;
+}
@@ -0,0 +1,391 @@
+use std::hash::Hash;
+
+use indexmap::{Equivalent, IndexMap};
+use rt_ref::{BorrowFail, Cell, Ref, RefMut};
+
+/// Dense storage variant of [`RtMap`].
+///
+/// Values are stored contiguously in insertion order, and the hash table
+/// only stores indices into that storage. Iterating over every entry is
+/// therefore cache friendly, at the cost of slightly slower removals.
+///
+/// [`RtMap`]: crate::RtMap
+#[derive(Debug)]
+pub struct RtIndexMap<K, V>(IndexMap<K, Cell<V>>);
+
+impl<K, V> Default for RtIndexMap<K, V> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+/// An [`IndexMap`] that allows multiple mutable borrows to different entries.
+///
+/// The [`borrow`] and [`borrow_mut`] methods take `&self`, allowing multiple
+/// mutable borrows of different entries at the same time. This is achieved via
+/// interior mutability. In case you violate the borrowing rules of Rust
+/// (multiple reads xor one write), you will get a panic.
+///
+/// For non-packing versions of these methods, use [`try_borrow`] and
+/// [`try_borrow_mut`].
+///
+/// [`borrow`]: Self::borrow
+/// [`borrow_mut`]: Self::borrow_mut
+/// [`try_borrow`]: Self::try_borrow
+/// [`try_borrow_mut`]: Self::try_borrow_mut
+impl<K, V> RtIndexMap<K, V>
+where
+    K: Hash + Eq,
+{
+    /// Creates an empty `RtIndexMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtIndexMap;
+    /// let mut map = RtIndexMap::<u32, String>::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty `RtIndexMap` with the specified capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtIndexMap;
+    /// let map: RtIndexMap<&str, i32> = RtIndexMap::with_capacity(10);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexMap::with_capacity(capacity))
+    }
+
+    /// Returns the number of elements the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Returns the underlying map.
+    pub fn into_inner(self) -> IndexMap<K, Cell<V>> {
+        self.0
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, the entry is appended and
+    /// [`None`] is returned.
+    ///
+    /// If the map did have this key present, the value is updated in place,
+    /// and the old value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtIndexMap;
+    ///
+    /// let mut map = RtIndexMap::new();
+    /// assert_eq!(map.insert(37, "a"), None);
+    /// assert_eq!(map.is_empty(), false);
+    ///
+    /// map.insert(37, "b");
+    /// assert_eq!(map.insert(37, "c"), Some("b"));
+    /// assert_eq!(*map.borrow(&37), "c");
+    /// ```
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        self.0.insert(k, Cell::new(v)).map(Cell::into_inner)
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Clears the map, removing all key-value pairs.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// The last entry is moved into the removed entry's position, so this
+    /// does not preserve insertion order, but is *O(1)*.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtIndexMap;
+    ///
+    /// let mut map = RtIndexMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.swap_remove(&1), Some("a"));
+    /// assert_eq!(map.swap_remove(&1), None);
+    /// ```
+    pub fn swap_remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.0.swap_remove(k).map(Cell::into_inner)
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// Entries after the removed entry are shifted down, so this preserves
+    /// insertion order, but is *O(n)*.
+    pub fn shift_remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.0.shift_remove(k).map(Cell::into_inner)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.0.contains_key(k)
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// See [`try_borrow`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the resource doesn't exist.
+    /// * Panics if the resource is being accessed mutably.
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    #[track_caller]
+    pub fn borrow<Q>(&self, k: &Q) -> Ref<'_, V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let Some(cell) = self.0.get(k) else {
+            borrow_panic!(k);
+        };
+        match cell.try_borrow() {
+            Ok(cell_ref) => Ref::new(cell_ref),
+            Err(_) => borrow_conflict_panic!(k, cell, "borrow"),
+        }
+    }
+
+    /// Returns a reference to the value if it exists and is not mutably
+    /// borrowed, `None` otherwise.
+    pub fn try_borrow<Q>(&self, k: &Q) -> Result<Ref<'_, V>, BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.0
+            .get(k)
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(|cell| cell.try_borrow().map(Ref::new))
+    }
+
+    /// Returns a reference to the value if it exists and is not borrowed,
+    /// `None` otherwise.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the resource doesn't exist.
+    /// * Panics if the resource is already accessed.
+    #[track_caller]
+    pub fn borrow_mut<Q>(&self, k: &Q) -> RefMut<'_, V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let Some(cell) = self.0.get(k) else {
+            borrow_panic!(k);
+        };
+        match cell.try_borrow_mut() {
+            Ok(cell_ref_mut) => RefMut::new(cell_ref_mut),
+            Err(_) => borrow_conflict_panic!(k, cell, "mutably borrow"),
+        }
+    }
+
+    /// Returns a mutable reference to `R` if it exists, `None` otherwise.
+    pub fn try_borrow_mut<Q>(&self, k: &Q) -> Result<RefMut<'_, V>, BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.0
+            .get(k)
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(|r_cell| r_cell.try_borrow_mut().map(RefMut::new))
+    }
+
+    /// Retrieves a resource without fetching, which is cheaper, but only
+    /// available with `&mut self`.
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.0.get_mut(k).map(Cell::get_mut)
+    }
+
+    /// Get raw access to the underlying cell.
+    pub fn get_raw<Q>(&self, k: &Q) -> Option<&Cell<V>>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.0.get(k)
+    }
+
+    /// Returns an iterator over the map's keys, in insertion order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.0.keys()
+    }
+
+    /// Returns an iterator over the map's entries, with mutable references to
+    /// the values, in insertion order.
+    ///
+    /// This takes `&mut self`, so values are accessed directly instead of
+    /// through runtime borrow guards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtIndexMap;
+    ///
+    /// let mut map = RtIndexMap::new();
+    /// map.insert('a', 1);
+    ///
+    /// map.iter_mut().for_each(|(_k, v)| *v += 1);
+    ///
+    /// assert_eq!(2, *map.borrow(&'a'));
+    /// ```
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+        self.0.iter_mut().map(|(k, cell)| (k, cell.get_mut()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rt_ref::BorrowFail;
+
+    use super::RtIndexMap;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Res;
+
+    #[test]
+    fn insert() {
+        let mut rt_index_map = RtIndexMap::new();
+        rt_index_map.insert('a', Res);
+
+        assert!(rt_index_map.contains_key(&'a'));
+        assert!(!rt_index_map.contains_key(&'b'));
+    }
+
+    #[test]
+    fn iter_mut_iterates_in_insertion_order() {
+        let mut rt_index_map = RtIndexMap::new();
+        rt_index_map.insert('c', 0);
+        rt_index_map.insert('a', 1);
+        rt_index_map.insert('b', 2);
+
+        let values = rt_index_map
+            .iter_mut()
+            .map(|(_k, v)| *v)
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![0, 1, 2], values);
+    }
+
+    #[test]
+    fn clear_removes_every_entry() {
+        let mut rt_index_map = RtIndexMap::new();
+        rt_index_map.insert('a', 0);
+        rt_index_map.insert('b', 1);
+        assert_eq!(2, rt_index_map.len());
+
+        rt_index_map.clear();
+
+        assert_eq!(0, rt_index_map.len());
+        assert!(rt_index_map.is_empty());
+    }
+
+    #[test]
+    fn swap_remove_moves_last_entry_into_removed_position() {
+        let mut rt_index_map = RtIndexMap::new();
+        rt_index_map.insert('a', 0);
+        rt_index_map.insert('b', 1);
+        rt_index_map.insert('c', 2);
+
+        assert_eq!(Some(0), rt_index_map.swap_remove(&'a'));
+
+        let keys = rt_index_map.keys().copied().collect::<Vec<_>>();
+        assert_eq!(vec!['c', 'b'], keys);
+    }
+
+    #[test]
+    fn shift_remove_preserves_insertion_order() {
+        let mut rt_index_map = RtIndexMap::new();
+        rt_index_map.insert('a', 0);
+        rt_index_map.insert('b', 1);
+        rt_index_map.insert('c', 2);
+
+        assert_eq!(Some(0), rt_index_map.shift_remove(&'a'));
+
+        let keys = rt_index_map.keys().copied().collect::<Vec<_>>();
+        assert_eq!(vec!['b', 'c'], keys);
+    }
+
+    #[test]
+    fn borrow_mut_different_entries_at_the_same_time() {
+        let mut rt_index_map = RtIndexMap::new();
+        rt_index_map.insert('a', 1);
+        rt_index_map.insert('b', 2);
+
+        let mut a = rt_index_map.borrow_mut(&'a');
+        let mut b = rt_index_map.borrow_mut(&'b');
+        *a += 1;
+        *b += 1;
+        drop(a);
+        drop(b);
+
+        assert_eq!(2, *rt_index_map.borrow(&'a'));
+        assert_eq!(3, *rt_index_map.borrow(&'b'));
+    }
+
+    #[test]
+    fn borrow_try_borrow_mut_returns_borrow_conflict_mut() {
+        let mut rt_index_map = RtIndexMap::new();
+        rt_index_map.insert('a', Res);
+
+        let _res = rt_index_map.borrow(&'a');
+
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictMut),
+            rt_index_map.try_borrow_mut(&'a')
+        );
+    }
+
+    #[test]
+    fn try_borrow_before_insert_returns_value_not_found() {
+        let rt_index_map = RtIndexMap::<char, Res>::new();
+
+        assert_eq!(
+            Err(BorrowFail::ValueNotFound),
+            rt_index_map.try_borrow(&'a')
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow a value with a `char` key, but the key does not exist."
+    )]
+    fn borrow_before_insert_panics() {
+        let rt_index_map = RtIndexMap::<char, i32>::new();
+
+        rt_index_map.borrow(&'a');
+    }
+}
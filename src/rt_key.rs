@@ -0,0 +1,76 @@
+/// Defines a strongly typed key newtype for use with [`RtMap`].
+///
+/// Keys of different domains that share a representation, such as `u64`
+/// texture and mesh IDs, can otherwise be mixed up without a compiler error.
+/// The generated type derives `Clone`, `Copy`, `Debug`, `PartialEq`, `Eq`,
+/// `PartialOrd`, `Ord`, and `Hash`, and converts to and from the wrapped
+/// type.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_map::{RtMap, rt_key};
+///
+/// rt_key! {
+///     /// Identifies a texture.
+///     pub struct TextureKey(pub u64);
+/// }
+///
+/// rt_key! {
+///     /// Identifies a mesh.
+///     pub struct MeshKey(pub u64);
+/// }
+///
+/// let mut textures = RtMap::<TextureKey, &str>::new();
+/// textures.insert(TextureKey::from(1), "grass.png");
+///
+/// assert_eq!("grass.png", *textures.borrow(&TextureKey(1)));
+/// assert_eq!(1, u64::from(TextureKey(1)));
+///
+/// // Does not compile: expected `TextureKey`, found `MeshKey`.
+/// // textures.borrow(&MeshKey(1));
+/// ```
+///
+/// [`RtMap`]: crate::RtMap
+#[macro_export]
+macro_rules! rt_key {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident($inner_vis:vis $inner:ty);
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name($inner_vis $inner);
+
+        impl ::core::convert::From<$inner> for $name {
+            fn from(inner: $inner) -> Self {
+                Self(inner)
+            }
+        }
+
+        impl ::core::convert::From<$name> for $inner {
+            fn from(key: $name) -> Self {
+                key.0
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RtMap;
+
+    rt_key! {
+        struct TextureKey(u64);
+    }
+
+    #[test]
+    fn rt_key_is_usable_as_map_key() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert(TextureKey::from(1), 'a');
+
+        assert_eq!('a', *rt_map.borrow(&TextureKey(1)));
+        assert!(!rt_map.contains_key(&TextureKey(2)));
+        assert_eq!(1, u64::from(TextureKey(1)));
+    }
+}
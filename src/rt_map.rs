@@ -1,34 +1,56 @@
 use std::{
-    borrow::Borrow,
-    collections::HashMap,
-    fmt,
-    hash::Hash,
-    ops::{Deref, DerefMut},
+    any::Any,
+    collections::HashSet,
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+    mem,
+    sync::{Mutex, PoisonError},
 };
 
-use rt_ref::{BorrowFail, Cell, Ref, RefMut};
+use hashbrown::{Equivalent, HashMap};
+use rt_ref::{BorrowFail, Cell, CellRef, CellRefMut, Ref, RefMut};
 
-use crate::Entry;
+#[cfg(feature = "stats")]
+use crate::{BorrowStats, borrow_stats::BorrowCounters};
+use crate::{
+    CapacityExceeded, ChangeSet, DefaultHashBuilder, Entry, EntryRef, FrozenRtMap, FrozenView,
+    IntoIter, KeyedBorrowFail, OccupiedFail, Project, ProjectionFail, RawEntryBuilder,
+    RawEntryBuilderMut, ReplaceKeyFail, RetainAction, RtMapBuilder, RtMapStats, Scope,
+    ShrinkPolicy, Transaction,
+};
 
 /// Map from `TypeId` to type.
 #[derive(Debug)]
-pub struct RtMap<K, V>(HashMap<K, Cell<V>>);
+pub struct RtMap<K, V, S = DefaultHashBuilder> {
+    /// The underlying map.
+    pub(crate) inner: HashMap<K, Cell<V>, S>,
+    /// When to release unused capacity after removals.
+    pub(crate) shrink_policy: ShrinkPolicy,
+    /// Maximum number of entries the map may hold.
+    pub(crate) max_entries: Option<usize>,
+    /// Keys queued by `remove_deferred`, removed by `flush_removals`.
+    pub(crate) deferred_removals: Mutex<Vec<K>>,
+    /// Counts of `try_borrow` and `try_borrow_mut` outcomes.
+    #[cfg(feature = "stats")]
+    pub(crate) borrow_counters: BorrowCounters,
+}
 
-impl<K, V> Default for RtMap<K, V> {
+impl<K, V, S> Default for RtMap<K, V, S>
+where
+    S: Default,
+{
     fn default() -> Self {
-        Self(Default::default())
+        Self {
+            inner: HashMap::default(),
+            shrink_policy: ShrinkPolicy::default(),
+            max_entries: None,
+            deferred_removals: Mutex::default(),
+            #[cfg(feature = "stats")]
+            borrow_counters: BorrowCounters::default(),
+        }
     }
 }
 
-macro_rules! borrow_panic {
-    ($key:ident) => {
-        panic!(
-            "Expected to borrow `{key:?}`, but it does not exist.",
-            key = $key
-        )
-    };
-}
-
 /// A [`HashMap`] that allows multiple mutable borrows to different entries.
 ///
 /// The [`borrow`] and [`borrow_mut`] methods take `&self`, allowing multiple
@@ -39,6 +61,27 @@ macro_rules! borrow_panic {
 /// For non-packing versions of these methods, use [`try_borrow`] and
 /// [`try_borrow_mut`].
 ///
+/// Lookup methods accept any key type that is [`Equivalent`] to `K`, so
+/// compound keys can be queried without constructing an owned key:
+///
+/// ```rust
+/// use rt_map::{Equivalent, RtMap};
+///
+/// #[derive(Hash)]
+/// struct KeyRef<'s>(&'s str, u32);
+///
+/// impl Equivalent<(String, u32)> for KeyRef<'_> {
+///     fn equivalent(&self, key: &(String, u32)) -> bool {
+///         self.0 == key.0 && self.1 == key.1
+///     }
+/// }
+///
+/// let mut rt_map = RtMap::new();
+/// rt_map.insert((String::from("a"), 1), 'a');
+///
+/// assert_eq!('a', *rt_map.try_borrow(&KeyRef("a", 1)).unwrap());
+/// ```
+///
 /// [`borrow`]: Self::borrow
 /// [`borrow_mut`]: Self::borrow_mut
 /// [`try_borrow`]: Self::try_borrow
@@ -74,9 +117,69 @@ where
     /// let map: RtMap<&str, i32> = RtMap::with_capacity(10);
     /// ```
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(HashMap::with_capacity(capacity))
+        Self::with_capacity_and_hasher(capacity, DefaultHashBuilder::default())
+    }
+
+    /// Returns a builder to configure a new `RtMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{RtMap, ShrinkPolicy};
+    ///
+    /// let map = RtMap::<u32, String>::builder()
+    ///     .with_shrink_policy(ShrinkPolicy::Fraction(4))
+    ///     .build();
+    /// ```
+    pub fn builder() -> RtMapBuilder<K, V> {
+        RtMapBuilder::new()
+    }
+}
+
+impl<K, V, S> RtMap<K, V, S> {
+    /// Creates an empty `RtMap` which will use the given hash builder to hash
+    /// keys.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::with_hasher(RandomState::new());
+    /// rt_map.insert('a', 1);
+    ///
+    /// assert_eq!(1, *rt_map.borrow(&'a'));
+    /// ```
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::with_capacity_and_hasher(0, hash_builder)
+    }
+
+    /// Creates an empty `RtMap` with the specified capacity, which will use
+    /// the given hash builder to hash keys.
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        Self {
+            inner: HashMap::with_capacity_and_hasher(capacity, hash_builder),
+            shrink_policy: ShrinkPolicy::default(),
+            max_entries: None,
+            deferred_removals: Mutex::default(),
+            #[cfg(feature = "stats")]
+            borrow_counters: BorrowCounters::default(),
+        }
+    }
+
+    /// Returns a reference to the map's hash builder.
+    pub fn hasher(&self) -> &S {
+        self.inner.hasher()
     }
+}
 
+impl<K, V, S> RtMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
     /// Returns the number of elements the map can hold without reallocating.
     ///
     /// This number is a lower bound; the `RtMap<K, V>` might be able to hold
@@ -90,430 +193,5608 @@ where
     /// assert!(map.capacity() >= 100);
     /// ```
     pub fn capacity(&self) -> usize {
-        self.0.capacity()
-    }
-
-    /// Returns the underlying map.
-    pub fn into_inner(self) -> HashMap<K, Cell<V>> {
-        self.0
-    }
-
-    /// Gets the given key’s corresponding entry in the map for in-place
-    /// manipulation.
-    pub fn entry(&mut self, k: K) -> Entry<'_, K, V> {
-        Entry::new(self.0.entry(k))
+        self.inner.capacity()
     }
 
-    /// Inserts a key-value pair into the map.
+    /// Reserves capacity for at least `additional` more elements to be
+    /// inserted without reallocating.
     ///
-    /// If the map did not have this key present, [`None`] is returned.
+    /// # Panics
     ///
-    /// If the map did have this key present, the value is updated, and the old
-    /// value is returned. The key is not updated, though; this matters for
-    /// types that can be `==` without being identical.
+    /// Panics if the new allocation size overflows `usize`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use rt_map::RtMap;
-    ///
-    /// let mut map = RtMap::new();
-    /// assert_eq!(map.insert(37, "a"), None);
-    /// assert_eq!(map.is_empty(), false);
-    ///
-    /// map.insert(37, "b");
-    /// assert_eq!(map.insert(37, "c"), Some("b"));
-    /// assert_eq!(*map.borrow(&37), "c");
+    /// let mut map: RtMap<&str, i32> = RtMap::new();
+    /// map.reserve(10);
+    /// assert!(map.capacity() >= 10);
     /// ```
-    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
-        self.0.insert(k, Cell::new(v)).map(Cell::into_inner)
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
     }
 
-    /// Returns `true` if the map contains no elements.
+    /// Shrinks the capacity of the map as much as possible.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use rt_map::RtMap;
-    ///
-    /// let mut a = RtMap::new();
-    /// assert!(a.is_empty());
-    /// a.insert(1, "a");
-    /// assert!(!a.is_empty());
+    /// let mut map: RtMap<i32, i32> = RtMap::with_capacity(100);
+    /// map.insert(1, 2);
+    /// map.insert(3, 4);
+    /// assert!(map.capacity() >= 100);
+    /// map.shrink_to_fit();
+    /// assert!(map.capacity() >= 2);
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit()
     }
 
-    /// Removes a key from the map, returning the value at the key if the key
-    /// was previously in the map.
+    /// Shrinks the capacity of the map with a lower limit.
     ///
-    /// The key may be any borrowed form of the map’s key type, but `Hash` and
-    /// `Eq` on the borrowed form must match those for the key type.
+    /// The capacity remains at least as large as both the length and
+    /// `min_capacity`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use rt_map::RtMap;
-    ///
-    /// let mut map = RtMap::new();
-    /// map.insert(1, "a");
-    /// assert_eq!(map.remove(&1), Some("a"));
-    /// assert_eq!(map.remove(&1), None);
+    /// let mut map: RtMap<i32, i32> = RtMap::with_capacity(100);
+    /// map.insert(1, 2);
+    /// map.insert(3, 4);
+    /// map.shrink_to(10);
+    /// assert!(map.capacity() >= 10);
+    /// map.shrink_to(0);
+    /// assert!(map.capacity() >= 2);
     /// ```
-    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
-    where
-        Q: ?Sized + Hash + Eq,
-        K: Borrow<Q>,
-    {
-        self.0.remove(k).map(Cell::into_inner)
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.inner.shrink_to(min_capacity)
     }
 
-    /// Returns `true` if the map contains a value for the specified key.
+    /// Returns the maximum number of entries the map may hold, if limited.
     ///
-    /// The key may be any borrowed form of the map’s key type, but [`Hash`] and
-    /// [`Eq`] on the borrowed form must match those for the key type.
-    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    /// See [`RtMapBuilder::with_max_entries`].
+    pub fn max_entries(&self) -> Option<usize> {
+        self.max_entries
+    }
+
+    /// Borrows the value in `cell`, panicking with a message for `k` if it is
+    /// being accessed mutably.
+    #[track_caller]
+    fn borrow_cell<'cell, Q>(k: &Q, cell: &'cell Cell<V>) -> CellRef<'cell, V>
     where
-        Q: ?Sized + Hash + Eq,
-        K: Borrow<Q>,
+        Q: ?Sized,
     {
-        self.0.contains_key(k)
+        match cell.try_borrow() {
+            Ok(cell_ref) => cell_ref,
+            Err(_) => borrow_conflict_panic!(k, cell, "borrow"),
+        }
     }
 
-    /// Returns a reference to the value corresponding to the key.
-    ///
-    /// The key may be any borrowed form of the map’s key type, but [`Hash`] and
-    /// [`Eq`] on the borrowed form must match those for the key type.
-    ///
-    /// See [`try_borrow`] for a non-panicking version of this function.
-    ///
-    /// # Panics
-    ///
-    /// * Panics if the resource doesn't exist.
-    /// * Panics if the resource is being accessed mutably.
-    ///
-    /// [`try_borrow`]: Self::try_borrow
-    pub fn borrow<Q>(&self, k: &Q) -> Ref<V>
+    /// Mutably borrows the value in `cell`, panicking with a message for `k` if
+    /// it is already accessed.
+    #[track_caller]
+    fn borrow_cell_mut<'cell, Q>(k: &Q, cell: &'cell Cell<V>) -> CellRefMut<'cell, V>
     where
-        Q: ?Sized + Hash + Eq + fmt::Debug,
-        K: Borrow<Q>,
+        Q: ?Sized,
     {
-        self.0
-            .get(k)
-            .map(|cell| Ref::new(cell.borrow()))
-            .unwrap_or_else(|| borrow_panic!(k))
+        match cell.try_borrow_mut() {
+            Ok(cell_ref_mut) => cell_ref_mut,
+            Err(_) => borrow_conflict_panic!(k, cell, "mutably borrow"),
+        }
     }
 
-    /// Returns a reference to the value if it exists and is not mutably
-    /// borrowed, `None` otherwise.
-    pub fn try_borrow<Q>(&self, k: &Q) -> Result<Ref<V>, BorrowFail>
+    /// Returns an error if `k` is a new key and the map is at its maximum
+    /// number of entries.
+    fn check_max_entries<Q>(&self, k: &Q) -> Result<(), CapacityExceeded>
     where
-        Q: ?Sized + Hash + Eq,
-        K: Borrow<Q>,
+        Q: ?Sized + Hash + Equivalent<K>,
     {
-        self.0
-            .get(k)
-            .ok_or(BorrowFail::ValueNotFound)
-            .and_then(|cell| cell.try_borrow().map(Ref::new))
+        match self.max_entries {
+            Some(max_entries) if self.inner.len() >= max_entries && !self.inner.contains_key(k) => {
+                Err(CapacityExceeded::new(max_entries))
+            }
+            _ => Ok(()),
+        }
     }
 
-    /// Returns a reference to the value if it exists and is not borrowed,
-    /// `None` otherwise.
+    /// Returns the underlying map.
     ///
-    /// # Panics
+    /// The map is a [`hashbrown`] `HashMap`, so the `hashbrown` version is
+    /// part of this crate's public API.
     ///
-    /// * Panics if the resource doesn't exist.
-    /// * Panics if the resource is already accessed.
-    pub fn borrow_mut<Q>(&self, k: &Q) -> RefMut<V>
-    where
-        Q: ?Sized + Hash + Eq + fmt::Debug,
-        K: Borrow<Q>,
-    {
-        self.0
-            .get(k)
-            .map(|cell| RefMut::new(cell.borrow_mut()))
-            .unwrap_or_else(|| borrow_panic!(k))
+    /// Removals queued by [`remove_deferred`] are applied first.
+    ///
+    /// [`hashbrown`]: crate::hashbrown
+    /// [`remove_deferred`]: Self::remove_deferred
+    pub fn into_inner(mut self) -> HashMap<K, Cell<V>, S> {
+        self.flush_removals();
+        self.inner
     }
 
-    /// Returns a mutable reference to `R` if it exists, `None` otherwise.
-    pub fn try_borrow_mut<Q>(&self, k: &Q) -> Result<RefMut<V>, BorrowFail>
+    /// Returns a copy of the map if no value is being accessed mutably.
+    ///
+    /// Each value is cloned through an immutable borrow, so values that are
+    /// immutably borrowed can still be cloned. The copy has the same limits,
+    /// shrink policy, and keys queued by [`remove_deferred`] as this map, and
+    /// its borrow statistics start at zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BorrowFail::BorrowConflictImm`] if any value is being
+    /// accessed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{BorrowFail, RtMap};
+    ///
+    /// let rt_map = RtMap::from([('a', 1), ('b', 2)]);
+    ///
+    /// let b = rt_map.borrow_mut(&'b');
+    /// assert_eq!(
+    ///     Err(BorrowFail::BorrowConflictImm),
+    ///     rt_map.try_clone().map(|_| ())
+    /// );
+    /// drop(b);
+    ///
+    /// let rt_map_clone = rt_map.try_clone().unwrap();
+    /// assert_eq!(2, *rt_map_clone.borrow(&'b'));
+    /// ```
+    ///
+    /// [`remove_deferred`]: Self::remove_deferred
+    pub fn try_clone(&self) -> Result<Self, BorrowFail>
     where
-        Q: ?Sized + Hash + Eq,
-        K: Borrow<Q>,
+        K: Clone,
+        V: Clone,
+        S: Clone,
     {
-        self.0
-            .get(k)
-            .ok_or(BorrowFail::ValueNotFound)
-            .and_then(|r_cell| r_cell.try_borrow_mut().map(RefMut::new))
-    }
+        let mut inner =
+            HashMap::with_capacity_and_hasher(self.inner.len(), self.inner.hasher().clone());
+        self.inner.iter().try_for_each(|(k, cell)| {
+            let v = cell.try_borrow()?;
+            inner.insert(k.clone(), Cell::new(V::clone(&v)));
+            Ok(())
+        })?;
 
-    /// Retrieves a resource without fetching, which is cheaper, but only
-    /// available with `&mut self`.
-    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
-    where
-        Q: ?Sized + Hash + Eq,
-        K: Borrow<Q>,
-    {
-        self.get_resource_mut(k)
+        Ok(Self {
+            inner,
+            shrink_policy: self.shrink_policy,
+            max_entries: self.max_entries,
+            deferred_removals: Mutex::new(
+                self.deferred_removals
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clone(),
+            ),
+            #[cfg(feature = "stats")]
+            borrow_counters: BorrowCounters::default(),
+        })
     }
 
-    /// Retrieves a resource without fetching, which is cheaper, but only
-    /// available with `&mut self`.
-    pub fn get_resource_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
-    where
-        Q: ?Sized + Hash + Eq,
-        K: Borrow<Q>,
-    {
-        self.0.get_mut(k).map(Cell::get_mut)
+    /// Returns the entries as a [`std::collections::HashMap`], with the values
+    /// moved out of their cells.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// use rt_map::RtMap;
+    ///
+    /// let rt_map = RtMap::from([('a', 1)]);
+    /// let hash_map: HashMap<char, u32> = rt_map.into_hash_map();
+    ///
+    /// assert_eq!(Some(&1), hash_map.get(&'a'));
+    /// ```
+    pub fn into_hash_map(self) -> std::collections::HashMap<K, V> {
+        self.into_iter().collect()
     }
 
-    /// Get raw access to the underlying cell.
-    pub fn get_raw<Q>(&self, k: &Q) -> Option<&Cell<V>>
-    where
-        Q: ?Sized + Hash + Eq,
-        K: Borrow<Q>,
-    {
-        self.0.get(k)
+    /// Returns an iterator over the keys, consuming the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let rt_map = RtMap::from([('a', 1), ('b', 2)]);
+    ///
+    /// let mut keys = rt_map.into_keys().collect::<Vec<_>>();
+    /// keys.sort();
+    ///
+    /// assert_eq!(vec!['a', 'b'], keys);
+    /// ```
+    pub fn into_keys(self) -> impl Iterator<Item = K> {
+        self.inner.into_keys()
     }
-}
-
-impl<K, V> Deref for RtMap<K, V> {
-    type Target = HashMap<K, Cell<V>>;
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+    /// Returns an iterator over the values, consuming the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let rt_map = RtMap::from([('a', 1), ('b', 2)]);
+    ///
+    /// let mut values = rt_map.into_values().collect::<Vec<_>>();
+    /// values.sort();
+    ///
+    /// assert_eq!(vec![1, 2], values);
+    /// ```
+    pub fn into_values(self) -> impl Iterator<Item = V> {
+        self.inner.into_values().map(Cell::into_inner)
     }
-}
 
-impl<K, V> DerefMut for RtMap<K, V> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+    /// Gets the given key’s corresponding entry in the map for in-place
+    /// manipulation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is a new key and the map is at its [maximum number of
+    /// entries]. See [`checked_entry`] for a non-panicking version of this
+    /// function.
+    ///
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    /// [`checked_entry`]: Self::checked_entry
+    pub fn entry(&mut self, k: K) -> Entry<'_, K, V, S> {
+        self.checked_entry(k)
+            .unwrap_or_else(|capacity_exceeded| capacity_panic!(capacity_exceeded))
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use rt_ref::BorrowFail;
-
-    use super::RtMap;
-
-    #[derive(Debug, Default, PartialEq)]
-    struct Res;
-
-    #[derive(Debug, Default, PartialEq)]
-    struct Value(u32);
-
-    #[test]
-    fn insert() {
-        let mut rt_map = RtMap::new();
-        rt_map.insert('a', Res);
 
-        assert!(rt_map.contains_key(&'a'));
-        assert!(!rt_map.contains_key(&'b'));
-    }
+    /// Gets the given key’s corresponding entry in the map for in-place
+    /// manipulation, or [`CapacityExceeded`] if `k` is a new key and the map
+    /// is at its [maximum number of entries].
+    ///
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn checked_entry(&mut self, k: K) -> Result<Entry<'_, K, V, S>, CapacityExceeded> {
+        self.check_max_entries(&k)?;
 
-    #[test]
-    fn with_capacity_reserves_enough_capacity() {
-        let map: RtMap<i32, i32> = RtMap::with_capacity(100);
-        assert!(map.capacity() >= 100);
+        Ok(Entry::new(self.inner.entry(k)))
     }
 
-    #[test]
-    fn into_inner() {
-        let mut rt_map = RtMap::new();
-        rt_map.insert('a', Res);
-        let inner_map = rt_map.into_inner();
+    /// Gets the given key’s corresponding entry in the map for in-place
+    /// manipulation, converting the key into `K` only if the entry is vacant.
+    ///
+    /// This avoids allocating an owned key, such as a `String`, when the
+    /// entry already exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is a new key and the map is at its [maximum number of
+    /// entries].
+    ///
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn entry_ref<'a, 'b, Q>(&'a mut self, k: &'b Q) -> EntryRef<'a, 'b, K, Q, V, S>
+    where
+        K: From<&'b Q>,
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        if let Err(capacity_exceeded) = self.check_max_entries(k) {
+            capacity_panic!(capacity_exceeded);
+        }
 
-        assert!(inner_map.contains_key(&'a'));
+        EntryRef::new(self.inner.entry_ref(k))
     }
 
-    #[test]
-    fn deref_and_deref_mut() {
-        let mut rt_map = RtMap::new();
-        rt_map.insert('a', 0);
-        rt_map.insert('b', 1);
+    /// Returns mutable references to the values for each key, inserting the
+    /// return value of `f` for keys that are missing.
+    ///
+    /// Keys are only converted into `K` when they are missing from the map.
+    /// This takes `&mut self`, so the returned references do not need
+    /// runtime borrow tracking.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the same key is passed more than once.
+    /// * Panics if the new keys would exceed the map's [maximum number of
+    ///   entries].
+    ///
+    /// The keys are checked before any entry is inserted, so the map is
+    /// unchanged when this panics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::<String, u32>::new();
+    /// rt_map.insert(String::from("b"), 1);
+    ///
+    /// let [a, b] = rt_map.entry_many_or_insert_with(["a", "b"], |_k| 0);
+    /// *a += 1;
+    /// *b += 1;
+    ///
+    /// assert_eq!(1, *rt_map.borrow("a"));
+    /// assert_eq!(2, *rt_map.borrow("b"));
+    /// ```
+    ///
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn entry_many_or_insert_with<'b, Q, F, const N: usize>(
+        &mut self,
+        keys: [&'b Q; N],
+        mut f: F,
+    ) -> [&mut V; N]
+    where
+        K: From<&'b Q>,
+        Q: ?Sized + Hash + Equivalent<K>,
+        F: FnMut(&Q) -> V,
+    {
+        let duplicate_panic = || -> ! {
+            panic!(
+                "Expected distinct keys, but `entry_many_or_insert_with` was passed a key more \
+                than once."
+            )
+        };
 
-        rt_map.iter_mut().for_each(|(_k, v)| *v.borrow_mut() += 1);
+        // Everything is checked before the map is modified.
+        let cells = keys.map(|k| self.inner.get(k));
+        cells.iter().enumerate().for_each(|(index, cell)| {
+            if let Some(cell) = cell {
+                if cells[..index]
+                    .iter()
+                    .flatten()
+                    .any(|cell_earlier| std::ptr::eq(*cell_earlier, *cell))
+                {
+                    duplicate_panic();
+                }
+            }
+        });
+        let new_keys: [Option<K>; N] =
+            std::array::from_fn(|index| cells[index].is_none().then(|| K::from(keys[index])));
+        new_keys.iter().enumerate().for_each(|(index, k)| {
+            if k.is_some() && new_keys[..index].contains(k) {
+                duplicate_panic();
+            }
+        });
+        let new_count = new_keys.iter().flatten().count();
+        if let Some(max_entries) = self.max_entries {
+            if self.inner.len() + new_count > max_entries {
+                let capacity_exceeded = CapacityExceeded::new(max_entries);
+                capacity_panic!(capacity_exceeded);
+            }
+        }
 
-        let a = rt_map.remove(&'a');
-        assert_eq!(Some(1), a);
+        self.inner.reserve(new_count);
+        new_keys.into_iter().zip(keys).for_each(|(k, q)| {
+            if let Some(k) = k {
+                self.inner.insert(k, Cell::new(f(q)));
+            }
+        });
 
-        let b = rt_map.iter().next();
-        assert_eq!(Some(2), b.map(|(_k, v)| *v.borrow()));
+        self.inner.get_many_mut(keys).map(|cell| {
+            cell.expect("Every key is present after insertion.")
+                .get_mut()
+        })
     }
 
-    #[test]
-    fn is_empty_returns_true_when_map_does_not_contain_items() {
-        let rt_map = RtMap::<char, u32>::new();
-
-        assert!(rt_map.is_empty());
+    /// Returns a builder to look up an entry by hash, without needing a key.
+    ///
+    /// This is useful when the hash is already known, or when keys are
+    /// compared in a way that `Equivalent` cannot express. See
+    /// [`RawEntryBuilder`] for an example.
+    pub fn raw_entry(&self) -> RawEntryBuilder<'_, K, V, S> {
+        RawEntryBuilder::new(self)
+    }
+
+    /// Returns a builder to look up an entry by hash for in-place
+    /// manipulation, only needing an owned key if the entry is vacant.
+    ///
+    /// See [`RawEntryMut`] for an example.
+    ///
+    /// [`RawEntryMut`]: crate::RawEntryMut
+    pub fn raw_entry_mut(&mut self) -> RawEntryBuilderMut<'_, K, V, S> {
+        let full_at = self
+            .max_entries
+            .filter(|max_entries| self.inner.len() >= *max_entries);
+
+        RawEntryBuilderMut::new(self.inner.raw_entry_mut(), full_at)
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, [`None`] is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned. The key is not updated, though; this matters for
+    /// types that can be `==` without being identical.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is a new key and the map is at its [maximum number of
+    /// entries]. See [`checked_insert`] for a non-panicking version of this
+    /// function.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut map = RtMap::new();
+    /// assert_eq!(map.insert(37, "a"), None);
+    /// assert_eq!(map.is_empty(), false);
+    ///
+    /// map.insert(37, "b");
+    /// assert_eq!(map.insert(37, "c"), Some("b"));
+    /// assert_eq!(*map.borrow(&37), "c");
+    /// ```
+    ///
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    /// [`checked_insert`]: Self::checked_insert
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        self.checked_insert(k, v)
+            .unwrap_or_else(|capacity_exceeded| capacity_panic!(capacity_exceeded))
+    }
+
+    /// Inserts a key-value pair into the map, or returns [`CapacityExceeded`]
+    /// if `k` is a new key and the map is at its [maximum number of
+    /// entries].
+    ///
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn checked_insert(&mut self, k: K, v: V) -> Result<Option<V>, CapacityExceeded> {
+        self.check_max_entries(&k)?;
+
+        Ok(self.inner.insert(k, Cell::new(v)).map(Cell::into_inner))
+    }
+
+    /// Inserts a key-value pair into the map if the key is not already
+    /// present, returning a mutable reference to the inserted value.
+    ///
+    /// If the key is already present, the map is not changed, and the key
+    /// and value are returned in an [`OccupiedFail`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is at its [maximum number of entries].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{OccupiedFail, RtMap};
+    ///
+    /// let mut rt_map = RtMap::new();
+    ///
+    /// assert_eq!(Ok(&mut 1), rt_map.try_insert('a', 1));
+    /// assert_eq!(
+    ///     Err(OccupiedFail { key: 'a', value: 2 }),
+    ///     rt_map.try_insert('a', 2)
+    /// );
+    /// assert_eq!(1, *rt_map.borrow(&'a'));
+    /// ```
+    ///
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn try_insert(&mut self, k: K, v: V) -> Result<&mut V, OccupiedFail<K, V>> {
+        if self.inner.contains_key(&k) {
+            return Err(OccupiedFail { key: k, value: v });
+        }
+
+        Ok(self.insert_unique_unchecked(k, v).1)
+    }
+
+    /// Inserts a key-value pair into the map without checking if the key
+    /// already exists in the map.
+    ///
+    /// Returns references to the key and value just inserted.
+    ///
+    /// This is intended for bulk loading keys that are already known to be
+    /// unique, such as when copying entries from another map. Unlike
+    /// [`insert`], it does not return a previous value.
+    ///
+    /// If the key already exists in the map, its value is replaced. This does
+    /// not cause memory unsafety, but is a logic error.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the map is at its [maximum number of entries].
+    /// * In debug builds, panics if the map already contains the key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut map = RtMap::new();
+    /// map.insert_unique_unchecked(1, "a");
+    /// map.insert_unique_unchecked(2, "b");
+    ///
+    /// assert_eq!(*map.borrow(&1), "a");
+    /// assert_eq!(*map.borrow(&2), "b");
+    /// ```
+    ///
+    /// [`insert`]: Self::insert
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn insert_unique_unchecked(&mut self, k: K, v: V) -> (&K, &mut V) {
+        if let Some(max_entries) = self.max_entries {
+            if self.inner.len() >= max_entries {
+                let capacity_exceeded = CapacityExceeded::new(max_entries);
+                capacity_panic!(capacity_exceeded);
+            }
+        }
+        debug_assert!(
+            !self.inner.contains_key(&k),
+            "`insert_unique_unchecked` called with a key that already exists in the map."
+        );
+
+        let (k, cell) = self
+            .inner
+            .raw_entry_mut()
+            .from_key(&k)
+            .insert(k, Cell::new(v))
+            .into_key_value();
+        (k, cell.get_mut())
+    }
+
+    /// Returns the number of entries in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut a = RtMap::new();
+    /// assert_eq!(0, a.len());
+    /// a.insert(1, "a");
+    /// assert_eq!(1, a.len());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut a = RtMap::new();
+    /// assert!(a.is_empty());
+    /// a.insert(1, "a");
+    /// assert!(!a.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map’s key type, or any type
+    /// that is [`Equivalent`] to it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut map = RtMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove(&1), Some("a"));
+    /// assert_eq!(map.remove(&1), None);
+    /// ```
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let value = self.inner.remove(k).map(Cell::into_inner);
+        self.shrink_if_needed();
+        value
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the
+    /// key was previously in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut map = RtMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove_entry(&1), Some((1, "a")));
+    /// assert_eq!(map.remove_entry(&1), None);
+    /// ```
+    pub fn remove_entry<Q>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let entry = self
+            .inner
+            .remove_entry(k)
+            .map(|(k, cell)| (k, cell.into_inner()));
+        self.shrink_if_needed();
+        entry
+    }
+
+    /// Queues a key to be removed by the next call to [`flush_removals`].
+    ///
+    /// This only needs `&self`, so entries can be scheduled for removal while
+    /// other values are borrowed, such as during iteration. The entry stays
+    /// in the map until the removals are flushed.
+    ///
+    /// The key is removed along with whatever value it has when the removals
+    /// are flushed. If the entry is removed and inserted again in between,
+    /// the new value is removed. [`clear`] discards the queued keys.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 0);
+    /// rt_map.insert('b', 1);
+    ///
+    /// rt_map.iter().for_each(|(k, v)| {
+    ///     if *v == 0 {
+    ///         rt_map.remove_deferred(*k);
+    ///     }
+    /// });
+    /// assert!(rt_map.contains_key(&'a'));
+    ///
+    /// rt_map.flush_removals();
+    /// assert!(!rt_map.contains_key(&'a'));
+    /// assert!(rt_map.contains_key(&'b'));
+    /// ```
+    ///
+    /// [`clear`]: Self::clear
+    /// [`flush_removals`]: Self::flush_removals
+    pub fn remove_deferred(&self, k: K) {
+        self.deferred_removals
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(k);
+    }
+
+    /// Removes the entries queued by [`remove_deferred`], returning the
+    /// number of entries that were removed.
+    ///
+    /// Keys that are not in the map, or were queued more than once, are
+    /// skipped.
+    ///
+    /// [`remove_deferred`]: Self::remove_deferred
+    pub fn flush_removals(&mut self) -> usize {
+        let deferred_removals = mem::take(
+            self.deferred_removals
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        let removed_count = deferred_removals
+            .iter()
+            .filter(|k| self.inner.remove(*k).is_some())
+            .count();
+        self.shrink_if_needed();
+
+        removed_count
+    }
+
+    /// Removes all entries from the map, keeping the allocated memory unless
+    /// the map's [`ShrinkPolicy`] releases it.
+    ///
+    /// Keys queued by [`remove_deferred`] are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut a = RtMap::new();
+    /// a.insert(1, "a");
+    /// a.clear();
+    /// assert!(a.is_empty());
+    /// ```
+    ///
+    /// [`remove_deferred`]: Self::remove_deferred
+    pub fn clear(&mut self) {
+        self.inner.clear();
+        self.deferred_removals
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        self.shrink_if_needed();
+    }
+
+    /// Moves the value at `old` to the key `new`, returning the previously
+    /// stored key.
+    ///
+    /// The value's cell is moved as is, so the value is not cloned.
+    ///
+    /// # Errors
+    ///
+    /// * Returns [`ReplaceKeyFail::KeyNotFound`] if `old` is not in the map.
+    /// * Returns [`ReplaceKeyFail::KeyExists`] if `new` is already in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{ReplaceKeyFail, RtMap};
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert(String::from("a"), 1);
+    /// rt_map.insert(String::from("b"), 2);
+    ///
+    /// assert_eq!(
+    ///     Ok(String::from("a")),
+    ///     rt_map.replace_key("a", String::from("c"))
+    /// );
+    /// assert_eq!(1, *rt_map.borrow("c"));
+    /// assert_eq!(
+    ///     Err(ReplaceKeyFail::KeyExists),
+    ///     rt_map.replace_key("c", String::from("b"))
+    /// );
+    /// ```
+    pub fn replace_key<Q>(&mut self, old: &Q, new: K) -> Result<K, ReplaceKeyFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        if self.inner.contains_key(&new) {
+            return Err(ReplaceKeyFail::KeyExists);
+        }
+        let (k_old, cell) = self
+            .inner
+            .remove_entry(old)
+            .ok_or(ReplaceKeyFail::KeyNotFound)?;
+        self.inner.insert(new, cell);
+
+        Ok(k_old)
+    }
+
+    /// Removes and returns an arbitrary entry, or `None` if the map is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut map = RtMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.pop_any(), Some((1, "a")));
+    /// assert_eq!(map.pop_any(), None);
+    /// ```
+    pub fn pop_any(&mut self) -> Option<(K, V)> {
+        let entry = self
+            .inner
+            .extract_if(|_k, _cell| true)
+            .next()
+            .map(|(k, cell)| (k, cell.into_inner()));
+        self.shrink_if_needed();
+        entry
+    }
+
+    /// Removes and returns the entry with the smallest key, or `None` if the
+    /// map is empty.
+    ///
+    /// This scans every key, so it is *O(n)*.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut map = RtMap::new();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    /// assert_eq!(map.pop_first(), Some((1, "a")));
+    /// assert_eq!(map.pop_first(), Some((2, "b")));
+    /// assert_eq!(map.pop_first(), None);
+    /// ```
+    pub fn pop_first(&mut self) -> Option<(K, V)>
+    where
+        K: Ord,
+    {
+        // The entry is found by address, since the key cannot be borrowed
+        // while the map is mutably borrowed to remove it.
+        let first = self.inner.keys().min().map(|k| k as *const K)?;
+        let entry = self
+            .inner
+            .extract_if(|k, _cell| std::ptr::eq(k, first))
+            .next()
+            .map(|(k, cell)| (k, cell.into_inner()));
+        self.shrink_if_needed();
+        entry
+    }
+
+    /// Shrinks the map's capacity if the [`ShrinkPolicy`] calls for it.
+    fn shrink_if_needed(&mut self) {
+        if let Some(capacity) = self
+            .shrink_policy
+            .shrink_to(self.inner.len(), self.inner.capacity())
+        {
+            self.inner.shrink_to(capacity);
+        }
+    }
+
+    /// Removes all entries from the map, returning them as an iterator of
+    /// owned key-value pairs.
+    ///
+    /// The map keeps its allocated memory, regardless of its
+    /// [`ShrinkPolicy`]. If the iterator is dropped before it is fully
+    /// consumed, the remaining entries are still removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    ///
+    /// assert_eq!(vec![('a', 1)], rt_map.drain().collect::<Vec<_>>());
+    /// assert!(rt_map.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> impl Iterator<Item = (K, V)> + '_ {
+        self.inner.drain().map(|(k, cell)| (k, cell.into_inner()))
+    }
+
+    /// Moves all entries into `other`, leaving this map empty.
+    ///
+    /// Entries in `other` with the same key are replaced. When `other` is
+    /// empty, the maps' allocations are swapped instead of moving each entry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if moving an entry would exceed `other`'s [maximum number of
+    /// entries].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut staging = RtMap::new();
+    /// staging.insert('a', 1);
+    /// staging.insert('b', 2);
+    ///
+    /// let mut live = RtMap::new();
+    /// live.insert('b', 0);
+    ///
+    /// staging.drain_into(&mut live);
+    ///
+    /// assert!(staging.is_empty());
+    /// assert_eq!(1, *live.borrow(&'a'));
+    /// assert_eq!(2, *live.borrow(&'b'));
+    /// ```
+    ///
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn drain_into(&mut self, other: &mut RtMap<K, V, S>) {
+        let other_fits = other
+            .max_entries
+            .is_none_or(|max_entries| self.inner.len() <= max_entries);
+        if other.inner.is_empty() && other_fits {
+            std::mem::swap(&mut self.inner, &mut other.inner);
+        } else {
+            other.inner.reserve(self.inner.len());
+            self.inner.drain().for_each(|(k, cell)| {
+                other.insert(k, cell.into_inner());
+            });
+        }
+        self.shrink_if_needed();
+    }
+
+    /// Moves the entries that match the predicate into `other`.
+    ///
+    /// Entries in `other` with the same key are replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if moving an entry would exceed `other`'s [maximum number of
+    /// entries].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut staging = RtMap::new();
+    /// staging.insert('a', 1);
+    /// staging.insert('b', 2);
+    ///
+    /// let mut live = RtMap::new();
+    /// staging.drain_into_if(&mut live, |_k, v| *v > 1);
+    ///
+    /// assert_eq!(1, *staging.borrow(&'a'));
+    /// assert_eq!(2, *live.borrow(&'b'));
+    /// ```
+    ///
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn drain_into_if<F>(&mut self, other: &mut RtMap<K, V, S>, mut pred: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.inner
+            .extract_if(|k, cell| pred(k, cell.get_mut()))
+            .for_each(|(k, cell)| {
+                other.insert(k, cell.into_inner());
+            });
+        self.shrink_if_needed();
+    }
+
+    /// Retains only the entries for which `f` returns `true`.
+    ///
+    /// Values are passed to `f` directly, as this takes `&mut self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// rt_map.retain(|_k, v| *v % 2 == 0);
+    ///
+    /// assert!(!rt_map.contains_key(&'a'));
+    /// assert!(rt_map.contains_key(&'b'));
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.inner.retain(|k, cell| f(k, cell.get_mut()));
+        self.shrink_if_needed();
+    }
+
+    /// Removes the entries for which `pred` returns `true`, returning them as
+    /// an iterator of owned key-value pairs.
+    ///
+    /// Entries are only checked as the iterator is consumed. If the iterator
+    /// is dropped before it is fully consumed, the remaining entries are kept.
+    /// The map's [`ShrinkPolicy`] is not applied.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let removed = rt_map.extract_if(|_k, v| *v % 2 == 0).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(vec![('b', 2)], removed);
+    /// assert!(rt_map.contains_key(&'a'));
+    /// ```
+    pub fn extract_if<'a, F>(&'a mut self, mut pred: F) -> impl Iterator<Item = (K, V)> + 'a
+    where
+        F: FnMut(&K, &mut V) -> bool + 'a,
+    {
+        self.inner
+            .extract_if(move |k, cell| pred(k, cell.get_mut()))
+            .map(|(k, cell)| (k, cell.into_inner()))
+    }
+
+    /// Retains the entries for which `f` returns [`RetainAction::Keep`], until
+    /// it returns [`RetainAction::Stop`].
+    ///
+    /// This is [`retain`] that can stop early: once `f` returns
+    /// [`RetainAction::Stop`], it is not called again, and the remaining
+    /// entries are kept. Entries are visited in arbitrary order, and values
+    /// may be replaced in place through the `&mut V` passed to `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{RetainAction, RtMap};
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    /// rt_map.insert('c', 3);
+    ///
+    /// rt_map.retain_until(|_k, v| {
+    ///     if *v % 2 == 0 {
+    ///         RetainAction::Remove
+    ///     } else {
+    ///         *v *= 10;
+    ///         RetainAction::Keep
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(10, *rt_map.borrow(&'a'));
+    /// assert!(!rt_map.contains_key(&'b'));
+    /// assert_eq!(30, *rt_map.borrow(&'c'));
+    /// ```
+    ///
+    /// [`retain`]: Self::retain
+    pub fn retain_until<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> RetainAction,
+    {
+        let mut stopped = false;
+        self.inner.retain(|k, cell| {
+            if stopped {
+                return true;
+            }
+
+            match f(k, cell.get_mut()) {
+                RetainAction::Keep => true,
+                RetainAction::Remove => false,
+                RetainAction::Stop => {
+                    stopped = true;
+                    true
+                }
+            }
+        });
+        self.shrink_if_needed();
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map’s key type, or any type
+    /// that is [`Equivalent`] to it.
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.inner.contains_key(k)
+    }
+
+    /// Returns `true` if the map contains a value equal to `v`.
+    ///
+    /// This scans every value, so it is *O(n)*. See [`try_contains_value`]
+    /// for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value is being accessed mutably before a match is found.
+    ///
+    /// [`try_contains_value`]: Self::try_contains_value
+    pub fn contains_value(&self, v: &V) -> bool
+    where
+        V: PartialEq,
+    {
+        self.inner
+            .iter()
+            .any(|(k, cell)| *Self::borrow_cell(k, cell) == *v)
+    }
+
+    /// Returns whether the map contains a value equal to `v`, or
+    /// [`BorrowFail::BorrowConflictImm`] if a value is being accessed mutably
+    /// before a match is found.
+    pub fn try_contains_value(&self, v: &V) -> Result<bool, BorrowFail>
+    where
+        V: PartialEq,
+    {
+        for cell in self.inner.values() {
+            if *cell.try_borrow()? == *v {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Returns the keys of values that match the predicate.
+    ///
+    /// This scans every value, so it is *O(n)*. The keys are returned in
+    /// arbitrary order. See [`try_find_keys_by_value`] for a non-panicking
+    /// version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any value is being accessed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    /// rt_map.insert('c', 1);
+    ///
+    /// let mut keys = rt_map.find_keys_by_value(|v| *v == 1);
+    /// keys.sort();
+    ///
+    /// assert_eq!(vec![&'a', &'c'], keys);
+    /// ```
+    ///
+    /// [`try_find_keys_by_value`]: Self::try_find_keys_by_value
+    pub fn find_keys_by_value<F>(&self, mut pred: F) -> Vec<&K>
+    where
+        F: FnMut(&V) -> bool,
+    {
+        self.inner
+            .iter()
+            .filter(|(k, cell)| pred(&Self::borrow_cell(*k, cell)))
+            .map(|(k, _cell)| k)
+            .collect()
+    }
+
+    /// Returns the keys of values that match the predicate, or
+    /// [`BorrowFail::BorrowConflictImm`] if any value is being accessed
+    /// mutably.
+    pub fn try_find_keys_by_value<F>(&self, mut pred: F) -> Result<Vec<&K>, BorrowFail>
+    where
+        F: FnMut(&V) -> bool,
+    {
+        self.inner
+            .iter()
+            .filter_map(|(k, cell)| match cell.try_borrow() {
+                Ok(v) => pred(&v).then_some(Ok(k)),
+                Err(borrow_fail) => Some(Err(borrow_fail)),
+            })
+            .collect()
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map’s key type, or any type
+    /// that is [`Equivalent`] to it.
+    ///
+    /// See [`try_borrow`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the resource doesn't exist.
+    /// * Panics if the resource is being accessed mutably.
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    #[track_caller]
+    pub fn borrow<Q>(&self, k: &Q) -> Ref<'_, V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let Some(cell) = self.inner.get(k) else {
+            borrow_panic!(k);
+        };
+        match cell.try_borrow() {
+            Ok(cell_ref) => Ref::new(cell_ref),
+            Err(_) => borrow_conflict_panic!(k, cell, "borrow"),
+        }
+    }
+
+    /// Returns a reference to the value if it exists and is not mutably
+    /// borrowed, `None` otherwise.
+    pub fn try_borrow<Q>(&self, k: &Q) -> Result<Ref<'_, V>, BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let result = self
+            .inner
+            .get(k)
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(|cell| cell.try_borrow().map(Ref::new));
+
+        #[cfg(feature = "stats")]
+        self.borrow_counters.record(&result);
+
+        result
+    }
+
+    /// Returns references to the values corresponding to each key, or `None`
+    /// for keys that are not in the map.
+    ///
+    /// The same key may be passed multiple times. See [`try_get_many`] for a
+    /// non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the resources is being accessed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let [a, b, c] = rt_map.get_many([&'a', &'b', &'c']);
+    ///
+    /// assert_eq!(Some(1), a.map(|a| *a));
+    /// assert_eq!(Some(2), b.map(|b| *b));
+    /// assert!(c.is_none());
+    /// ```
+    ///
+    /// [`try_get_many`]: Self::try_get_many
+    #[track_caller]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<Ref<'_, V>>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        keys.map(|k| {
+            self.inner
+                .get(k)
+                .map(|cell| Ref::new(Self::borrow_cell(k, cell)))
+        })
+    }
+
+    /// Returns references to the values corresponding to each key, or the
+    /// reason each value could not be borrowed.
+    pub fn try_get_many<Q, const N: usize>(
+        &self,
+        keys: [&Q; N],
+    ) -> [Result<Ref<'_, V>, BorrowFail>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        keys.map(|k| self.try_borrow(k))
+    }
+
+    /// Returns mutable references to the values corresponding to each key.
+    ///
+    /// See [`try_borrow_many_mut`] for a version of this function that
+    /// returns an error when a value cannot be borrowed.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the same key is passed more than once.
+    /// * Panics if any of the resources doesn't exist.
+    /// * Panics if any of the resources is already accessed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let [mut a, mut b] = rt_map.borrow_many_mut([&'a', &'b']);
+    /// std::mem::swap(&mut *a, &mut *b);
+    /// drop([a, b]);
+    ///
+    /// assert_eq!(2, *rt_map.borrow(&'a'));
+    /// assert_eq!(1, *rt_map.borrow(&'b'));
+    /// ```
+    ///
+    /// [`try_borrow_many_mut`]: Self::try_borrow_many_mut
+    #[track_caller]
+    pub fn borrow_many_mut<Q, const N: usize>(&self, keys: [&Q; N]) -> [RefMut<'_, V>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let cells = self.distinct_cells(keys, "borrow_many_mut");
+        std::array::from_fn(|index| {
+            let k = keys[index];
+            let Some(cell) = cells[index] else {
+                borrow_panic!(k);
+            };
+            match cell.try_borrow_mut() {
+                Ok(cell_ref_mut) => RefMut::new(cell_ref_mut),
+                Err(_) => borrow_conflict_panic!(k, cell, "mutably borrow"),
+            }
+        })
+    }
+
+    /// Returns mutable references to the values corresponding to each key,
+    /// or the first [`BorrowFail`] if any value cannot be borrowed.
+    ///
+    /// Either all values are borrowed, or none are.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the same key is passed more than once, as that can never
+    /// succeed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{BorrowFail, RtMap};
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let b = rt_map.borrow(&'b');
+    /// assert_eq!(
+    ///     Err(BorrowFail::BorrowConflictMut),
+    ///     rt_map.try_borrow_many_mut([&'a', &'b']).map(|_| ())
+    /// );
+    /// drop(b);
+    ///
+    /// assert!(rt_map.try_borrow_many_mut([&'a', &'b']).is_ok());
+    /// ```
+    pub fn try_borrow_many_mut<Q, const N: usize>(
+        &self,
+        keys: [&Q; N],
+    ) -> Result<[RefMut<'_, V>; N], BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let cells = self.distinct_cells(keys, "try_borrow_many_mut");
+        let results = cells.map(|cell| {
+            let result = cell
+                .ok_or(BorrowFail::ValueNotFound)
+                .and_then(|cell| cell.try_borrow_mut().map(RefMut::new));
+
+            #[cfg(feature = "stats")]
+            self.borrow_counters.record(&result);
+
+            result
+        });
+        if let Some(borrow_fail) = results
+            .iter()
+            .find_map(|result| result.as_ref().err().copied())
+        {
+            return Err(borrow_fail);
+        }
+
+        Ok(results.map(|result| result.expect("Borrow failures were checked before mapping.")))
+    }
+
+    /// Returns the cells for each of the `keys`, or `None` for keys that are
+    /// not in the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two keys refer to the same entry.
+    #[track_caller]
+    fn distinct_cells<Q, const N: usize>(
+        &self,
+        keys: [&Q; N],
+        method: &str,
+    ) -> [Option<&Cell<V>>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let cells = keys.map(|k| self.inner.get(k));
+        cells.iter().enumerate().for_each(|(index, cell)| {
+            if let Some(cell) = cell {
+                if cells[..index]
+                    .iter()
+                    .flatten()
+                    .any(|earlier_cell| std::ptr::eq(*earlier_cell, *cell))
+                {
+                    panic!(
+                        "Expected distinct keys, but `{method}` was passed a key more than once."
+                    );
+                }
+            }
+        });
+
+        cells
+    }
+
+    /// Returns mutable references to the values corresponding to two keys.
+    ///
+    /// See [`try_borrow2_mut`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if either resource doesn't exist.
+    /// * Panics if either resource is already accessed, or both keys are the
+    ///   same.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let (mut a, mut b) = rt_map.borrow2_mut(&'a', &'b');
+    /// *a += *b;
+    /// *b = 0;
+    /// drop((a, b));
+    ///
+    /// assert_eq!(3, *rt_map.borrow(&'a'));
+    /// assert_eq!(0, *rt_map.borrow(&'b'));
+    /// ```
+    ///
+    /// [`try_borrow2_mut`]: Self::try_borrow2_mut
+    #[track_caller]
+    pub fn borrow2_mut<Q1, Q2>(&self, k1: &Q1, k2: &Q2) -> (RefMut<'_, V>, RefMut<'_, V>)
+    where
+        Q1: ?Sized + Hash + Equivalent<K>,
+        Q2: ?Sized + Hash + Equivalent<K>,
+    {
+        (self.borrow_mut(k1), self.borrow_mut(k2))
+    }
+
+    /// Returns mutable references to the values corresponding to two keys,
+    /// or the first [`BorrowFail`] if either value cannot be borrowed.
+    ///
+    /// Either both values are borrowed, or neither is.
+    pub fn try_borrow2_mut<Q1, Q2>(
+        &self,
+        k1: &Q1,
+        k2: &Q2,
+    ) -> Result<(RefMut<'_, V>, RefMut<'_, V>), BorrowFail>
+    where
+        Q1: ?Sized + Hash + Equivalent<K>,
+        Q2: ?Sized + Hash + Equivalent<K>,
+    {
+        Ok((self.try_borrow_mut(k1)?, self.try_borrow_mut(k2)?))
+    }
+
+    /// Returns mutable references to the values corresponding to three keys.
+    ///
+    /// See [`try_borrow3_mut`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if any of the resources doesn't exist.
+    /// * Panics if any of the resources is already accessed, or any two keys
+    ///   are the same.
+    ///
+    /// [`try_borrow3_mut`]: Self::try_borrow3_mut
+    #[track_caller]
+    pub fn borrow3_mut<Q1, Q2, Q3>(
+        &self,
+        k1: &Q1,
+        k2: &Q2,
+        k3: &Q3,
+    ) -> (RefMut<'_, V>, RefMut<'_, V>, RefMut<'_, V>)
+    where
+        Q1: ?Sized + Hash + Equivalent<K>,
+        Q2: ?Sized + Hash + Equivalent<K>,
+        Q3: ?Sized + Hash + Equivalent<K>,
+    {
+        (
+            self.borrow_mut(k1),
+            self.borrow_mut(k2),
+            self.borrow_mut(k3),
+        )
+    }
+
+    /// Returns mutable references to the values corresponding to three keys,
+    /// or the first [`BorrowFail`] if any value cannot be borrowed.
+    ///
+    /// Either all values are borrowed, or none are.
+    #[allow(clippy::type_complexity)]
+    pub fn try_borrow3_mut<Q1, Q2, Q3>(
+        &self,
+        k1: &Q1,
+        k2: &Q2,
+        k3: &Q3,
+    ) -> Result<(RefMut<'_, V>, RefMut<'_, V>, RefMut<'_, V>), BorrowFail>
+    where
+        Q1: ?Sized + Hash + Equivalent<K>,
+        Q2: ?Sized + Hash + Equivalent<K>,
+        Q3: ?Sized + Hash + Equivalent<K>,
+    {
+        Ok((
+            self.try_borrow_mut(k1)?,
+            self.try_borrow_mut(k2)?,
+            self.try_borrow_mut(k3)?,
+        ))
+    }
+
+    /// Returns a reference to the value corresponding to `k1`, and a mutable
+    /// reference to the value corresponding to `k2`.
+    ///
+    /// See [`try_borrow_and_borrow_mut`] for a non-panicking version of this
+    /// function.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if either resource doesn't exist.
+    /// * Panics if `k1`'s resource is being accessed mutably, or `k2`'s
+    ///   resource is already accessed, or both keys are the same.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert("velocity", 2);
+    /// rt_map.insert("position", 0);
+    ///
+    /// let (velocity, mut position) = rt_map.borrow_and_borrow_mut("velocity", "position");
+    /// *position += *velocity;
+    /// drop((velocity, position));
+    ///
+    /// assert_eq!(2, *rt_map.borrow("position"));
+    /// ```
+    ///
+    /// [`try_borrow_and_borrow_mut`]: Self::try_borrow_and_borrow_mut
+    #[track_caller]
+    pub fn borrow_and_borrow_mut<Q1, Q2>(&self, k1: &Q1, k2: &Q2) -> (Ref<'_, V>, RefMut<'_, V>)
+    where
+        Q1: ?Sized + Hash + Equivalent<K>,
+        Q2: ?Sized + Hash + Equivalent<K>,
+    {
+        (self.borrow(k1), self.borrow_mut(k2))
+    }
+
+    /// Returns a reference to the value corresponding to `k1`, and a mutable
+    /// reference to the value corresponding to `k2`, or the first
+    /// [`BorrowFail`] if either value cannot be borrowed.
+    ///
+    /// Either both values are borrowed, or neither is.
+    pub fn try_borrow_and_borrow_mut<Q1, Q2>(
+        &self,
+        k1: &Q1,
+        k2: &Q2,
+    ) -> Result<(Ref<'_, V>, RefMut<'_, V>), BorrowFail>
+    where
+        Q1: ?Sized + Hash + Equivalent<K>,
+        Q2: ?Sized + Hash + Equivalent<K>,
+    {
+        Ok((self.try_borrow(k1)?, self.try_borrow_mut(k2)?))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key,
+    /// inserting `V::default()` if the key is missing.
+    ///
+    /// This is a shorthand for `entry(k).or_insert_with(V::default)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is a new key and the map is at its [maximum number of
+    /// entries].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::<char, Vec<u32>>::new();
+    ///
+    /// rt_map.borrow_mut_or_default('a').push(1);
+    /// rt_map.borrow_mut_or_default('a').push(2);
+    ///
+    /// assert_eq!(vec![1, 2], *rt_map.borrow(&'a'));
+    /// ```
+    ///
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn borrow_mut_or_default(&mut self, k: K) -> RefMut<'_, V>
+    where
+        V: Default,
+    {
+        self.entry(k).or_insert_with(V::default)
+    }
+
+    /// Returns the stored key and a reference to the value corresponding to
+    /// the key, or `None` if the key is not in the map.
+    ///
+    /// This is useful when the stored key carries data that is not compared
+    /// by `Eq`, such as the original casing of a case-insensitive key. See
+    /// [`try_get_key_value`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource is being accessed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert(1, "a");
+    ///
+    /// let (k, v) = rt_map.get_key_value(&1).unwrap();
+    /// assert_eq!((&1, "a"), (k, *v));
+    /// assert!(rt_map.get_key_value(&2).is_none());
+    /// ```
+    ///
+    /// [`try_get_key_value`]: Self::try_get_key_value
+    #[track_caller]
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&K, Ref<'_, V>)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.inner
+            .get_key_value(k)
+            .map(|(k, cell)| (k, Ref::new(Self::borrow_cell(k, cell))))
+    }
+
+    /// Returns the stored key and a reference to the value if it exists and
+    /// is not mutably borrowed.
+    pub fn try_get_key_value<Q>(&self, k: &Q) -> Result<(&K, Ref<'_, V>), BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.inner
+            .get_key_value(k)
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(|(k, cell)| cell.try_borrow().map(|cell_ref| (k, Ref::new(cell_ref))))
+    }
+
+    /// Returns a reference to the value if it exists and is not borrowed,
+    /// `None` otherwise.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the resource doesn't exist.
+    /// * Panics if the resource is already accessed.
+    #[track_caller]
+    pub fn borrow_mut<Q>(&self, k: &Q) -> RefMut<'_, V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let Some(cell) = self.inner.get(k) else {
+            borrow_panic!(k);
+        };
+        match cell.try_borrow_mut() {
+            Ok(cell_ref_mut) => RefMut::new(cell_ref_mut),
+            Err(_) => borrow_conflict_panic!(k, cell, "mutably borrow"),
+        }
+    }
+
+    /// Returns a mutable reference to `R` if it exists, `None` otherwise.
+    pub fn try_borrow_mut<Q>(&self, k: &Q) -> Result<RefMut<'_, V>, BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let result = self
+            .inner
+            .get(k)
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(|r_cell| r_cell.try_borrow_mut().map(RefMut::new));
+
+        #[cfg(feature = "stats")]
+        self.borrow_counters.record(&result);
+
+        result
+    }
+
+    /// Returns a reference to the value for `k`, with the key in the panic
+    /// message if it cannot be borrowed.
+    ///
+    /// This is [`borrow`] for keys that implement `Debug`. [`borrow`] only
+    /// includes the key's type in its panic message.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the resource doesn't exist.
+    /// * Panics if the resource is being accessed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,should_panic
+    /// use rt_map::RtMap;
+    ///
+    /// let rt_map = RtMap::<String, u32>::new();
+    ///
+    /// // Panics with:
+    /// // "Expected to borrow the value for `\"a\"`, but the key does not exist."
+    /// rt_map.borrow_keyed("a");
+    /// ```
+    ///
+    /// [`borrow`]: Self::borrow
+    #[track_caller]
+    pub fn borrow_keyed<Q>(&self, k: &Q) -> Ref<'_, V>
+    where
+        Q: ?Sized + Hash + Equivalent<K> + Debug,
+    {
+        let Some(cell) = self.inner.get(k) else {
+            borrow_panic!(k, debug);
+        };
+        match cell.try_borrow() {
+            Ok(cell_ref) => Ref::new(cell_ref),
+            Err(_) => borrow_conflict_panic!(k, cell, "borrow", debug),
+        }
+    }
+
+    /// Returns a mutable reference to the value for `k`, with the key in the
+    /// panic message if it cannot be borrowed mutably.
+    ///
+    /// This is [`borrow_mut`] for keys that implement `Debug`.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the resource doesn't exist.
+    /// * Panics if the resource is already accessed.
+    ///
+    /// [`borrow_mut`]: Self::borrow_mut
+    #[track_caller]
+    pub fn borrow_mut_keyed<Q>(&self, k: &Q) -> RefMut<'_, V>
+    where
+        Q: ?Sized + Hash + Equivalent<K> + Debug,
+    {
+        let Some(cell) = self.inner.get(k) else {
+            borrow_panic!(k, debug);
+        };
+        match cell.try_borrow_mut() {
+            Ok(cell_ref_mut) => RefMut::new(cell_ref_mut),
+            Err(_) => borrow_conflict_panic!(k, cell, "mutably borrow", debug),
+        }
+    }
+
+    /// Returns a reference to the value for `k`, or a [`KeyedBorrowFail`]
+    /// holding an owned copy of `k` if it cannot be borrowed.
+    ///
+    /// This is [`try_borrow`] with an error that can be reported without
+    /// losing which key failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`KeyedBorrowFail`] with the [`BorrowFail`] from
+    /// [`try_borrow`] if the value cannot be borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let rt_map = RtMap::<String, u32>::new();
+    ///
+    /// let keyed_borrow_fail = rt_map.try_borrow_keyed("a").unwrap_err();
+    /// assert_eq!("a", keyed_borrow_fail.key);
+    /// assert_eq!(
+    ///     "Failed to borrow the value for `\"a\"`, as it does not exist.",
+    ///     keyed_borrow_fail.to_string()
+    /// );
+    /// ```
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    pub fn try_borrow_keyed<Q>(&self, k: &Q) -> Result<Ref<'_, V>, KeyedBorrowFail<Q::Owned>>
+    where
+        Q: ?Sized + Hash + Equivalent<K> + ToOwned,
+    {
+        self.try_borrow(k).map_err(|borrow_fail| KeyedBorrowFail {
+            key: k.to_owned(),
+            borrow_fail,
+        })
+    }
+
+    /// Returns a mutable reference to the value for `k`, or a
+    /// [`KeyedBorrowFail`] holding an owned copy of `k` if it cannot be
+    /// borrowed mutably.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`KeyedBorrowFail`] with the [`BorrowFail`] from
+    /// [`try_borrow_mut`] if the value cannot be borrowed mutably.
+    ///
+    /// [`try_borrow_mut`]: Self::try_borrow_mut
+    pub fn try_borrow_mut_keyed<Q>(&self, k: &Q) -> Result<RefMut<'_, V>, KeyedBorrowFail<Q::Owned>>
+    where
+        Q: ?Sized + Hash + Equivalent<K> + ToOwned,
+    {
+        self.try_borrow_mut(k)
+            .map_err(|borrow_fail| KeyedBorrowFail {
+                key: k.to_owned(),
+                borrow_fail,
+            })
+    }
+
+    /// Runs `f` with a reference to the value for `k`, and returns its result.
+    ///
+    /// The value is only borrowed while `f` runs, so there is no guard that
+    /// needs to be dropped before the value can be borrowed mutably.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`BorrowFail`] from [`try_borrow`] if the value cannot be
+    /// borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    ///
+    /// assert_eq!(Ok(2), rt_map.with(&'a', |a| *a + 1));
+    /// ```
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    pub fn with<Q, F, R>(&self, k: &Q, f: F) -> Result<R, BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+        F: FnOnce(&V) -> R,
+    {
+        self.try_borrow(k).map(|v| f(&v))
+    }
+
+    /// Runs `f` with a mutable reference to the value for `k`, and returns its
+    /// result.
+    ///
+    /// The value is only borrowed while `f` runs, so there is no guard that
+    /// needs to be dropped before the value can be borrowed again.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`BorrowFail`] from [`try_borrow_mut`] if the value cannot
+    /// be borrowed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let b = rt_map.with(&'b', |b| *b).unwrap();
+    /// rt_map.with_mut(&'a', |a| *a += b).unwrap();
+    ///
+    /// assert_eq!(Ok(3), rt_map.with(&'a', |a| *a));
+    /// ```
+    ///
+    /// [`try_borrow_mut`]: Self::try_borrow_mut
+    pub fn with_mut<Q, F, R>(&self, k: &Q, f: F) -> Result<R, BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+        F: FnOnce(&mut V) -> R,
+    {
+        self.try_borrow_mut(k).map(|mut v| f(&mut v))
+    }
+
+    /// Returns a clone of the value for `k`, or `None` if the key is not in
+    /// the map.
+    ///
+    /// The value is only borrowed while it is cloned. See [`try_get_cloned`]
+    /// for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource is being accessed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', String::from("a"));
+    ///
+    /// assert_eq!(Some(String::from("a")), rt_map.get_cloned(&'a'));
+    /// assert_eq!(None, rt_map.get_cloned(&'b'));
+    /// ```
+    ///
+    /// [`try_get_cloned`]: Self::try_get_cloned
+    #[track_caller]
+    pub fn get_cloned<Q>(&self, k: &Q) -> Option<V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+        V: Clone,
+    {
+        self.inner
+            .get(k)
+            .map(|cell| V::clone(&Self::borrow_cell(k, cell)))
+    }
+
+    /// Returns a clone of the value for `k`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`BorrowFail`] from [`try_borrow`] if the value cannot be
+    /// borrowed.
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    pub fn try_get_cloned<Q>(&self, k: &Q) -> Result<V, BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+        V: Clone,
+    {
+        self.with(k, V::clone)
+    }
+
+    /// Replaces the value for `k` with `v`, and returns the old value.
+    ///
+    /// This only needs `&self`, as the value is swapped through a temporary
+    /// mutable borrow. The key stays in the map.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`BorrowFail`] from [`try_borrow_mut`] if the value cannot
+    /// be borrowed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    ///
+    /// assert_eq!(Ok(1), rt_map.replace(&'a', 2));
+    /// assert_eq!(2, *rt_map.borrow(&'a'));
+    /// ```
+    ///
+    /// [`try_borrow_mut`]: Self::try_borrow_mut
+    pub fn replace<Q>(&self, k: &Q, v: V) -> Result<V, BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.with_mut(k, |value| mem::replace(value, v))
+    }
+
+    /// Takes the value for `k`, leaving `V::default()` in its place.
+    ///
+    /// This only needs `&self`, and the key stays in the map.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`BorrowFail`] from [`try_borrow_mut`] if the value cannot
+    /// be borrowed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', vec![1, 2]);
+    ///
+    /// assert_eq!(Ok(vec![1, 2]), rt_map.take(&'a'));
+    /// assert!(rt_map.borrow(&'a').is_empty());
+    /// ```
+    ///
+    /// [`try_borrow_mut`]: Self::try_borrow_mut
+    pub fn take<Q>(&self, k: &Q) -> Result<V, BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+        V: Default,
+    {
+        self.with_mut(k, mem::take)
+    }
+
+    /// Swaps the values for two keys.
+    ///
+    /// This only needs `&self`, and does not remove or reinsert either key.
+    /// Like [`slice::swap`], swapping a key with itself does nothing, as long
+    /// as its value can be borrowed mutably.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`BorrowFail`] from [`try_borrow2_mut`] if either value
+    /// cannot be borrowed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// rt_map.swap_values(&'a', &'b').unwrap();
+    ///
+    /// assert_eq!(2, *rt_map.borrow(&'a'));
+    /// assert_eq!(1, *rt_map.borrow(&'b'));
+    /// ```
+    ///
+    /// [`try_borrow2_mut`]: Self::try_borrow2_mut
+    pub fn swap_values<Q1, Q2>(&self, k1: &Q1, k2: &Q2) -> Result<(), BorrowFail>
+    where
+        Q1: ?Sized + Hash + Equivalent<K>,
+        Q2: ?Sized + Hash + Equivalent<K>,
+    {
+        let same_key = match (self.inner.get(k1), self.inner.get(k2)) {
+            (Some(cell1), Some(cell2)) => std::ptr::eq(cell1, cell2),
+            _ => false,
+        };
+        if same_key {
+            return self.try_borrow_mut(k1).map(|_v| ());
+        }
+
+        let (mut v1, mut v2) = self.try_borrow2_mut(k1, k2)?;
+        mem::swap(&mut *v1, &mut *v2);
+        Ok(())
+    }
+
+    /// Returns the counts of [`try_borrow`] and [`try_borrow_mut`] outcomes
+    /// since the map was created or the stats were last reset.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{BorrowStats, RtMap};
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    ///
+    /// let _a = rt_map.try_borrow(&'a');
+    /// let _b = rt_map.try_borrow(&'b');
+    ///
+    /// let borrow_stats = rt_map.borrow_stats();
+    /// assert_eq!(
+    ///     BorrowStats {
+    ///         hits: 1,
+    ///         not_found: 1,
+    ///         conflicts: 0,
+    ///     },
+    ///     borrow_stats
+    /// );
+    /// assert_eq!(Some(0.5), borrow_stats.hit_rate());
+    /// ```
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    /// [`try_borrow_mut`]: Self::try_borrow_mut
+    #[cfg(feature = "stats")]
+    pub fn borrow_stats(&self) -> BorrowStats {
+        self.borrow_counters.stats()
+    }
+
+    /// Resets the counts returned by [`borrow_stats`] to zero.
+    ///
+    /// [`borrow_stats`]: Self::borrow_stats
+    #[cfg(feature = "stats")]
+    pub fn reset_borrow_stats(&self) {
+        self.borrow_counters.reset();
+    }
+
+    /// Returns the number of entries, and with the `"stats"` feature, the
+    /// `RtMap::borrow_stats` counts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// assert_eq!(2, rt_map.stats().len);
+    /// ```
+    pub fn stats(&self) -> RtMapStats {
+        RtMapStats {
+            len: self.inner.len(),
+            #[cfg(feature = "stats")]
+            borrow_stats: self.borrow_counters.stats(),
+        }
+    }
+
+    /// Runs `f` with a [`Scope`] to borrow values through, releasing every
+    /// borrow when `f` returns.
+    ///
+    /// Borrows made through the scope cannot be returned from or stored
+    /// outside of `f`, so they do not need to be dropped manually.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// rt_map.scope(|scope| {
+    ///     let mut a = scope.borrow_mut(&'a');
+    ///     let mut b = scope.borrow_mut(&'b');
+    ///     *a += 1;
+    ///     *b += 1;
+    /// });
+    ///
+    /// // No need to drop `a` and `b`.
+    /// assert_eq!(2, *rt_map.borrow(&'a'));
+    /// assert_eq!(3, *rt_map.borrow(&'b'));
+    /// ```
+    ///
+    /// Borrows cannot escape the scope:
+    ///
+    /// ```rust,compile_fail
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    ///
+    /// let a = rt_map.scope(|scope| scope.borrow(&'a'));
+    /// ```
+    pub fn scope<F, R>(&self, f: F) -> R
+    where
+        F: for<'scope> FnOnce(&'scope Scope<'scope, '_, K, V, S>) -> R,
+    {
+        f(&Scope::new(self))
+    }
+
+    /// Returns a [`Transaction`] that buffers changes to this map, and only
+    /// applies them when committed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    ///
+    /// let mut transaction = rt_map.transaction();
+    /// transaction.update('a', |a| *a += 1);
+    /// transaction.insert('b', 2);
+    /// transaction.commit();
+    ///
+    /// assert_eq!(2, *rt_map.borrow(&'a'));
+    /// assert_eq!(2, *rt_map.borrow(&'b'));
+    ///
+    /// let mut transaction = rt_map.transaction();
+    /// transaction.remove('a');
+    /// drop(transaction);
+    ///
+    /// assert!(rt_map.contains_key(&'a'));
+    /// ```
+    pub fn transaction(&mut self) -> Transaction<'_, K, V, S> {
+        Transaction::new(self)
+    }
+
+    /// Returns a [`FrozenView`] that hands out plain references to the
+    /// values, without runtime borrow checks.
+    ///
+    /// Creating the view visits every entry once. Lookups through the view
+    /// are then cheaper than [`borrow`], which is useful for read-heavy
+    /// phases where no values are mutated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let frozen_view = rt_map.snapshot();
+    /// let a: &u32 = frozen_view.get(&'a').unwrap();
+    /// let b: &u32 = frozen_view.get(&'b').unwrap();
+    ///
+    /// assert_eq!(3, a + b);
+    /// ```
+    ///
+    /// [`borrow`]: Self::borrow
+    pub fn snapshot(&mut self) -> FrozenView<'_, K, V, S>
+    where
+        S: Clone,
+    {
+        let mut entries =
+            HashMap::with_capacity_and_hasher(self.inner.len(), self.inner.hasher().clone());
+        entries.extend(self.inner.iter_mut().map(|(k, cell)| (k, &*cell.get_mut())));
+
+        FrozenView::new(entries)
+    }
+
+    /// Returns a [`FrozenRtMap`] that hands out plain references to the
+    /// values, and cannot be mutated.
+    ///
+    /// Unlike [`snapshot`], this consumes the map, so the frozen map can be
+    /// stored and shared on its own. Use [`FrozenRtMap::thaw`] to get the
+    /// `RtMap` back.
+    ///
+    /// Removals queued by [`remove_deferred`] are applied first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert("volume", 7);
+    ///
+    /// let frozen_rt_map = rt_map.freeze();
+    /// let volume: &u32 = frozen_rt_map.get("volume").unwrap();
+    ///
+    /// assert_eq!(7, *volume);
+    /// ```
+    ///
+    /// [`remove_deferred`]: Self::remove_deferred
+    /// [`snapshot`]: Self::snapshot
+    pub fn freeze(mut self) -> FrozenRtMap<K, V, S>
+    where
+        S: Clone,
+    {
+        self.flush_removals();
+
+        let Self {
+            inner: cells,
+            shrink_policy,
+            max_entries,
+            ..
+        } = self;
+
+        let mut inner = HashMap::with_capacity_and_hasher(cells.len(), cells.hasher().clone());
+        cells.into_iter().for_each(|(k, cell)| {
+            inner.insert(k, cell.into_inner());
+        });
+
+        FrozenRtMap {
+            inner,
+            shrink_policy,
+            max_entries,
+        }
+    }
+
+    /// Returns a reference to the part of the value projected by
+    /// [`Project<T>`], such as an enum variant's payload.
+    ///
+    /// See [`try_borrow_variant`] for a non-panicking version of this
+    /// function.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the resource doesn't exist.
+    /// * Panics if the resource does not project to a `T`.
+    /// * Panics if the resource is being accessed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{Project, ProjectionFail, RtMap};
+    ///
+    /// enum Shape {
+    ///     Circle(f32),
+    ///     Square(u32),
+    /// }
+    ///
+    /// impl Project<f32> for Shape {
+    ///     fn project(&self) -> Option<&f32> {
+    ///         match self {
+    ///             Shape::Circle(radius) => Some(radius),
+    ///             Shape::Square(_) => None,
+    ///         }
+    ///     }
+    ///
+    ///     fn project_mut(&mut self) -> Option<&mut f32> {
+    ///         match self {
+    ///             Shape::Circle(radius) => Some(radius),
+    ///             Shape::Square(_) => None,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('c', Shape::Circle(1.0));
+    /// rt_map.insert('s', Shape::Square(2));
+    ///
+    /// *rt_map.borrow_mut_variant::<f32, _>(&'c') *= 2.0;
+    ///
+    /// assert_eq!(2.0, *rt_map.borrow_variant::<f32, _>(&'c'));
+    /// assert_eq!(
+    ///     Err(ProjectionFail::Mismatch),
+    ///     rt_map
+    ///         .try_borrow_variant::<f32, _>(&'s')
+    ///         .map(|radius| *radius)
+    /// );
+    /// ```
+    ///
+    /// [`try_borrow_variant`]: Self::try_borrow_variant
+    #[track_caller]
+    pub fn borrow_variant<T, Q>(&self, k: &Q) -> Ref<'_, T>
+    where
+        V: Project<T>,
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let Some(cell) = self.inner.get(k) else {
+            borrow_panic!(k);
+        };
+        let cell_ref = Self::borrow_cell(k, cell);
+        if cell_ref.project().is_none() {
+            projection_panic!(k, T);
+        }
+
+        Ref::new(cell_ref.map(|value| {
+            value
+                .project()
+                .expect("Value projection was checked before mapping.")
+        }))
+    }
+
+    /// Returns a reference to the part of the value projected by
+    /// [`Project<T>`] if it exists, projects to a `T`, and is not mutably
+    /// borrowed.
+    pub fn try_borrow_variant<T, Q>(&self, k: &Q) -> Result<Ref<'_, T>, ProjectionFail>
+    where
+        V: Project<T>,
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let cell_ref = self
+            .inner
+            .get(k)
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(Cell::try_borrow)?;
+        if cell_ref.project().is_none() {
+            return Err(ProjectionFail::Mismatch);
+        }
+
+        Ok(Ref::new(cell_ref.map(|value| {
+            value
+                .project()
+                .expect("Value projection was checked before mapping.")
+        })))
+    }
+
+    /// Returns a mutable reference to the part of the value projected by
+    /// [`Project<T>`], such as an enum variant's payload.
+    ///
+    /// See [`try_borrow_mut_variant`] for a non-panicking version of this
+    /// function.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the resource doesn't exist.
+    /// * Panics if the resource does not project to a `T`.
+    /// * Panics if the resource is already accessed.
+    ///
+    /// [`try_borrow_mut_variant`]: Self::try_borrow_mut_variant
+    #[track_caller]
+    pub fn borrow_mut_variant<T, Q>(&self, k: &Q) -> RefMut<'_, T>
+    where
+        V: Project<T>,
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let Some(cell) = self.inner.get(k) else {
+            borrow_panic!(k);
+        };
+        let mut cell_ref_mut = Self::borrow_cell_mut(k, cell);
+        if cell_ref_mut.project_mut().is_none() {
+            projection_panic!(k, T);
+        }
+
+        RefMut::new(cell_ref_mut.map(|value| {
+            value
+                .project_mut()
+                .expect("Value projection was checked before mapping.")
+        }))
+    }
+
+    /// Returns a mutable reference to the part of the value projected by
+    /// [`Project<T>`] if it exists, projects to a `T`, and is not borrowed.
+    pub fn try_borrow_mut_variant<T, Q>(&self, k: &Q) -> Result<RefMut<'_, T>, ProjectionFail>
+    where
+        V: Project<T>,
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let mut cell_ref_mut = self
+            .inner
+            .get(k)
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(Cell::try_borrow_mut)?;
+        if cell_ref_mut.project_mut().is_none() {
+            return Err(ProjectionFail::Mismatch);
+        }
+
+        Ok(RefMut::new(cell_ref_mut.map(|value| {
+            value
+                .project_mut()
+                .expect("Value projection was checked before mapping.")
+        })))
+    }
+
+    /// Returns a reference to the value mapped by `f`, such as a trait object
+    /// that the value implements.
+    ///
+    /// [`Ref`] can only hold sized types, so this returns a [`CellRef`], which
+    /// can hold unsized types such as `dyn Trait`. See [`try_borrow_map`] for
+    /// a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the resource doesn't exist.
+    /// * Panics if the resource is being accessed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::fmt::Display;
+    ///
+    /// use rt_map::{CellRef, RtMap};
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1u32);
+    ///
+    /// let a: CellRef<'_, dyn Display> = rt_map.borrow_map(&'a', |v| v as &dyn Display);
+    ///
+    /// assert_eq!("1", a.to_string());
+    /// ```
+    ///
+    /// [`try_borrow_map`]: Self::try_borrow_map
+    #[track_caller]
+    pub fn borrow_map<U, Q, F>(&self, k: &Q, f: F) -> CellRef<'_, U>
+    where
+        U: ?Sized,
+        Q: ?Sized + Hash + Equivalent<K>,
+        F: FnOnce(&V) -> &U,
+    {
+        let Some(cell) = self.inner.get(k) else {
+            borrow_panic!(k);
+        };
+        Self::borrow_cell(k, cell).map(f)
+    }
+
+    /// Returns a reference to the value mapped by `f` if it exists and is not
+    /// mutably borrowed.
+    pub fn try_borrow_map<U, Q, F>(&self, k: &Q, f: F) -> Result<CellRef<'_, U>, BorrowFail>
+    where
+        U: ?Sized,
+        Q: ?Sized + Hash + Equivalent<K>,
+        F: FnOnce(&V) -> &U,
+    {
+        self.inner
+            .get(k)
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(|cell| cell.try_borrow().map(|cell_ref| cell_ref.map(f)))
+    }
+
+    /// Returns a mutable reference to the value mapped by `f`, such as a trait
+    /// object that the value implements.
+    ///
+    /// See [`try_borrow_mut_map`] for a non-panicking version of this
+    /// function.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the resource doesn't exist.
+    /// * Panics if the resource is already accessed.
+    ///
+    /// [`try_borrow_mut_map`]: Self::try_borrow_mut_map
+    #[track_caller]
+    pub fn borrow_mut_map<U, Q, F>(&self, k: &Q, f: F) -> CellRefMut<'_, U>
+    where
+        U: ?Sized,
+        Q: ?Sized + Hash + Equivalent<K>,
+        F: FnOnce(&mut V) -> &mut U,
+    {
+        let Some(cell) = self.inner.get(k) else {
+            borrow_panic!(k);
+        };
+        Self::borrow_cell_mut(k, cell).map(f)
+    }
+
+    /// Returns a mutable reference to the value mapped by `f` if it exists
+    /// and is not borrowed.
+    pub fn try_borrow_mut_map<U, Q, F>(&self, k: &Q, f: F) -> Result<CellRefMut<'_, U>, BorrowFail>
+    where
+        U: ?Sized,
+        Q: ?Sized + Hash + Equivalent<K>,
+        F: FnOnce(&mut V) -> &mut U,
+    {
+        self.inner
+            .get(k)
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(|cell| {
+                cell.try_borrow_mut()
+                    .map(|cell_ref_mut| cell_ref_mut.map(f))
+            })
+    }
+
+    /// Retrieves a resource without fetching, which is cheaper, but only
+    /// available with `&mut self`.
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.get_resource_mut(k)
+    }
+
+    /// Retrieves a resource without fetching, which is cheaper, but only
+    /// available with `&mut self`.
+    pub fn get_resource_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.inner.get_mut(k).map(Cell::get_mut)
+    }
+
+    /// Returns mutable references to the values for each of the `keys`, or
+    /// `None` for keys that are not in the map.
+    ///
+    /// This is the [`get_mut`] counterpart to [`borrow_many_mut`], and does
+    /// not go through runtime borrow guards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the same key is passed more than once. Keys that are not in
+    /// the map are not checked.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let [a, b, c] = rt_map.get_disjoint_mut([&'a', &'b', &'c']);
+    /// if let (Some(a), Some(b)) = (a, b) {
+    ///     std::mem::swap(a, b);
+    /// }
+    ///
+    /// assert!(c.is_none());
+    /// assert_eq!(2, *rt_map.borrow(&'a'));
+    /// assert_eq!(1, *rt_map.borrow(&'b'));
+    /// ```
+    ///
+    /// [`borrow_many_mut`]: Self::borrow_many_mut
+    /// [`get_mut`]: Self::get_mut
+    pub fn get_disjoint_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> [Option<&mut V>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.inner
+            .get_many_mut(keys)
+            .map(|cell| cell.map(Cell::get_mut))
+    }
+
+    /// Returns a mutable reference to the value for `k`, inserting `default`
+    /// if the key is missing.
+    ///
+    /// This is the [`get_mut`] counterpart to `entry(k).or_insert(default)`,
+    /// and does not go through a runtime borrow guard.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is a new key and the map is at its [maximum number of
+    /// entries].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    ///
+    /// *rt_map.get_or_insert_mut('a', 0) += 1;
+    /// *rt_map.get_or_insert_mut('a', 0) += 1;
+    ///
+    /// assert_eq!(2, *rt_map.borrow(&'a'));
+    /// ```
+    ///
+    /// [`get_mut`]: Self::get_mut
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn get_or_insert_mut(&mut self, k: K, default: V) -> &mut V {
+        if let Err(capacity_exceeded) = self.check_max_entries(&k) {
+            capacity_panic!(capacity_exceeded);
+        }
+
+        self.inner
+            .entry(k)
+            .or_insert_with(move || Cell::new(default))
+            .get_mut()
+    }
+
+    /// Get raw access to the underlying cell.
+    pub fn get_raw<Q>(&self, k: &Q) -> Option<&Cell<V>>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.inner.get(k)
+    }
+
+    /// Returns an iterator over the map's keys, in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    ///
+    /// assert_eq!(vec![&'a'], rt_map.keys().collect::<Vec<_>>());
+    /// ```
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.inner.keys()
+    }
+
+    /// Returns an iterator over the map's entries, borrowing each value
+    /// immutably.
+    ///
+    /// Values are borrowed as the iterator reaches them, in arbitrary order.
+    /// See [`try_iter`] for a non-panicking version of this function, and
+    /// [`iter_available`] to skip values that are being accessed mutably.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the iterator reaches a value that is being accessed
+    /// mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let sum = rt_map.iter().map(|(_k, v)| *v).sum::<u32>();
+    ///
+    /// assert_eq!(3, sum);
+    /// ```
+    ///
+    /// [`try_iter`]: Self::try_iter
+    /// [`iter_available`]: Self::iter_available
+    pub fn iter(&self) -> impl Iterator<Item = (&K, Ref<'_, V>)> {
+        self.inner
+            .iter()
+            .map(|(k, cell)| (k, Ref::new(Self::borrow_cell(k, cell))))
+    }
+
+    /// Returns an iterator over the map's entries, with a reference to each
+    /// value if it is not mutably borrowed, and the [`BorrowFail`] otherwise.
+    ///
+    /// Values are borrowed as the iterator reaches them, in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{BorrowFail, RtMap};
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    ///
+    /// let _a = rt_map.borrow_mut(&'a');
+    ///
+    /// let entries = rt_map
+    ///     .try_iter()
+    ///     .map(|(k, v)| (*k, v.map(|v| *v)))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('a', Err(BorrowFail::BorrowConflictImm))], entries);
+    /// ```
+    pub fn try_iter(&self) -> impl Iterator<Item = (&K, Result<Ref<'_, V>, BorrowFail>)> {
+        self.inner
+            .iter()
+            .map(|(k, cell)| (k, cell.try_borrow().map(Ref::new)))
+    }
+
+    /// Returns an iterator over the map's entries, with a mutable reference
+    /// to each value if it is not borrowed, and the [`BorrowFail`] otherwise.
+    ///
+    /// Values are borrowed as the iterator reaches them, in arbitrary order.
+    /// This takes `&self`, so other values may be borrowed while iterating.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{BorrowFail, RtMap};
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let b = rt_map.borrow(&'b');
+    ///
+    /// rt_map.try_iter_mut().for_each(|(k, v)| match v {
+    ///     Ok(mut v) => *v += 10,
+    ///     Err(borrow_fail) => assert_eq!(('b', BorrowFail::BorrowConflictMut), (*k, borrow_fail)),
+    /// });
+    /// drop(b);
+    ///
+    /// assert_eq!(11, *rt_map.borrow(&'a'));
+    /// assert_eq!(2, *rt_map.borrow(&'b'));
+    /// ```
+    pub fn try_iter_mut(&self) -> impl Iterator<Item = (&K, Result<RefMut<'_, V>, BorrowFail>)> {
+        self.inner
+            .iter()
+            .map(|(k, cell)| (k, cell.try_borrow_mut().map(RefMut::new)))
+    }
+
+    /// Returns an iterator over the map's values, borrowing each value
+    /// immutably.
+    ///
+    /// Values are borrowed as the iterator reaches them, in arbitrary order.
+    /// See [`try_values`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the iterator reaches a value that is being accessed
+    /// mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// assert_eq!(3, rt_map.values().map(|v| *v).sum::<u32>());
+    /// ```
+    ///
+    /// [`try_values`]: Self::try_values
+    pub fn values(&self) -> impl Iterator<Item = Ref<'_, V>> {
+        self.inner
+            .iter()
+            .map(|(k, cell)| Ref::new(Self::borrow_cell(k, cell)))
+    }
+
+    /// Returns an iterator over the map's values, with a reference to each
+    /// value if it is not mutably borrowed, and the [`BorrowFail`] otherwise.
+    ///
+    /// Values are borrowed as the iterator reaches them, in arbitrary order.
+    pub fn try_values(&self) -> impl Iterator<Item = Result<Ref<'_, V>, BorrowFail>> {
+        self.inner
+            .values()
+            .map(|cell| cell.try_borrow().map(Ref::new))
+    }
+
+    /// Returns the map's keys in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('b', 2);
+    /// rt_map.insert('a', 1);
+    ///
+    /// assert_eq!(vec![&'a', &'b'], rt_map.keys_sorted());
+    /// ```
+    pub fn keys_sorted(&self) -> Vec<&K>
+    where
+        K: Ord,
+    {
+        let mut keys = self.inner.keys().collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// Returns an iterator over the map's entries in ascending key order.
+    ///
+    /// The keys are sorted up front, and each value is borrowed as the
+    /// iterator reaches it.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the iterator reaches a value that is being accessed
+    /// mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('b', 2);
+    /// rt_map.insert('a', 1);
+    ///
+    /// let entries = rt_map
+    ///     .iter_sorted()
+    ///     .map(|(k, v)| (*k, *v))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(vec![('a', 1), ('b', 2)], entries);
+    /// ```
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (&K, Ref<'_, V>)>
+    where
+        K: Ord,
+    {
+        let mut entries = self.inner.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(k, _cell)| *k);
+        entries
+            .into_iter()
+            .map(|(k, cell)| (k, Ref::new(Self::borrow_cell(k, cell))))
+    }
+
+    /// Returns an iterator over the entries whose values can be borrowed
+    /// immutably, skipping values that are being accessed mutably.
+    ///
+    /// Values are borrowed as the iterator reaches them, in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let _b = rt_map.borrow_mut(&'b');
+    ///
+    /// let entries = rt_map
+    ///     .iter_available()
+    ///     .map(|(k, v)| (*k, *v))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('a', 1)], entries);
+    /// ```
+    pub fn iter_available(&self) -> impl Iterator<Item = (&K, Ref<'_, V>)> {
+        self.inner.iter().filter_map(|(k, cell)| {
+            cell.try_borrow()
+                .ok()
+                .map(|cell_ref| (k, Ref::new(cell_ref)))
+        })
+    }
+
+    /// Returns an iterator over the entries whose values are not borrowed,
+    /// borrowing each value mutably and skipping values that are in use.
+    ///
+    /// Values are borrowed as the iterator reaches them, in arbitrary order.
+    pub fn iter_available_mut(&self) -> impl Iterator<Item = (&K, RefMut<'_, V>)> {
+        self.inner.iter().filter_map(|(k, cell)| {
+            cell.try_borrow_mut()
+                .ok()
+                .map(|cell_ref_mut| (k, RefMut::new(cell_ref_mut)))
+        })
+    }
+
+    /// Returns an iterator over mutable references to the values, in
+    /// arbitrary order.
+    ///
+    /// This takes `&mut self`, so values are accessed directly instead of
+    /// through runtime borrow guards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// rt_map.values_mut().for_each(|v| *v = 0);
+    ///
+    /// assert_eq!(0, *rt_map.borrow(&'a'));
+    /// assert_eq!(0, *rt_map.borrow(&'b'));
+    /// ```
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.inner.values_mut().map(Cell::get_mut)
+    }
+
+    /// Returns an iterator over the map's entries, with mutable references to
+    /// the values, in arbitrary order.
+    ///
+    /// This takes `&mut self`, so values are accessed directly instead of
+    /// through runtime borrow guards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    ///
+    /// rt_map.iter_mut().for_each(|(_k, v)| *v += 1);
+    ///
+    /// assert_eq!(2, *rt_map.borrow(&'a'));
+    /// ```
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+        self.inner.iter_mut().map(|(k, cell)| (k, cell.get_mut()))
+    }
+
+    /// Returns references to the values that match the predicate.
+    ///
+    /// Each value is borrowed once, and the borrow is kept if the predicate
+    /// matches. Values that are being accessed mutably cannot be checked, and
+    /// are returned with the [`BorrowFail`]. Entries are returned in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{BorrowFail, RtMap};
+    ///
+    /// #[derive(PartialEq)]
+    /// enum State {
+    ///     Ready,
+    ///     Waiting,
+    /// }
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', State::Ready);
+    /// rt_map.insert('b', State::Waiting);
+    /// rt_map.insert('c', State::Ready);
+    ///
+    /// let _c = rt_map.borrow_mut(&'c');
+    /// let mut selected = rt_map
+    ///     .select(|_k, state| *state == State::Ready)
+    ///     .into_iter()
+    ///     .map(|(k, result)| (*k, result.map(|_state| ())))
+    ///     .collect::<Vec<_>>();
+    /// selected.sort_by_key(|(k, _result)| *k);
+    ///
+    /// assert_eq!(
+    ///     vec![('a', Ok(())), ('c', Err(BorrowFail::BorrowConflictImm))],
+    ///     selected
+    /// );
+    /// ```
+    pub fn select<F>(&self, mut pred: F) -> Vec<(&K, Result<Ref<'_, V>, BorrowFail>)>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.inner
+            .iter()
+            .filter_map(|(k, cell)| match cell.try_borrow() {
+                Ok(cell_ref) => pred(k, &cell_ref).then(|| (k, Ok(Ref::new(cell_ref)))),
+                Err(borrow_fail) => Some((k, Err(borrow_fail))),
+            })
+            .collect()
+    }
+
+    /// Returns mutable references to the values that match the predicate.
+    ///
+    /// Each value is borrowed mutably once, and the borrow is kept if the
+    /// predicate matches. Values that are already borrowed cannot be checked,
+    /// and are returned with the [`BorrowFail`]. Entries are returned in
+    /// arbitrary order.
+    pub fn select_mut<F>(&self, mut pred: F) -> Vec<(&K, Result<RefMut<'_, V>, BorrowFail>)>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.inner
+            .iter()
+            .filter_map(|(k, cell)| match cell.try_borrow_mut() {
+                Ok(cell_ref_mut) => {
+                    pred(k, &cell_ref_mut).then(|| (k, Ok(RefMut::new(cell_ref_mut))))
+                }
+                Err(borrow_fail) => Some((k, Err(borrow_fail))),
+            })
+            .collect()
+    }
+
+    /// Folds every entry into an accumulator, borrowing each value
+    /// immutably while `f` runs.
+    ///
+    /// Entries are visited in arbitrary order. See [`try_fold_borrow`] for a
+    /// non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any value is being accessed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let sum = rt_map.fold_borrow(0, |sum, _k, v| sum + v);
+    ///
+    /// assert_eq!(3, sum);
+    /// ```
+    ///
+    /// [`try_fold_borrow`]: Self::try_fold_borrow
+    pub fn fold_borrow<B, F>(&self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &K, &V) -> B,
+    {
+        self.inner.iter().fold(init, |acc, (k, cell)| {
+            f(acc, k, &Self::borrow_cell(k, cell))
+        })
+    }
+
+    /// Folds every entry into an accumulator, borrowing each value
+    /// immutably while `f` runs, and stopping at the first error.
+    ///
+    /// If a value is being accessed mutably, folding stops and the
+    /// [`BorrowFail`] is returned, converted into `E`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{BorrowFail, RtMap};
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let sum = rt_map.try_fold_borrow(0, |sum, _k, v| Ok::<_, BorrowFail>(sum + v));
+    /// assert_eq!(Ok(3), sum);
+    ///
+    /// let _b = rt_map.borrow_mut(&'b');
+    /// let sum = rt_map.try_fold_borrow(0, |sum, _k, v| Ok::<_, BorrowFail>(sum + v));
+    /// assert_eq!(Err(BorrowFail::BorrowConflictImm), sum);
+    /// ```
+    pub fn try_fold_borrow<B, E, F>(&self, init: B, mut f: F) -> Result<B, E>
+    where
+        E: From<BorrowFail>,
+        F: FnMut(B, &K, &V) -> Result<B, E>,
+    {
+        self.inner.iter().try_fold(init, |acc, (k, cell)| {
+            let v = cell.try_borrow()?;
+            f(acc, k, &v)
+        })
+    }
+
+    /// Returns a map from each value to its key.
+    ///
+    /// When multiple keys have equal values, `resolve` is called with the
+    /// value, the key chosen so far, and the next key, and returns the key to
+    /// keep. Keys are visited in arbitrary order.
+    ///
+    /// See [`try_invert`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any value is being accessed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut ids = RtMap::new();
+    /// ids.insert(1, "alice");
+    /// ids.insert(2, "bob");
+    /// ids.insert(3, "alice");
+    ///
+    /// // Keep the smallest id for duplicate names.
+    /// let names = ids.invert(|_name, id_existing, id| id_existing.min(id));
+    ///
+    /// assert_eq!(1, *names.borrow("alice"));
+    /// assert_eq!(2, *names.borrow("bob"));
+    /// ```
+    ///
+    /// [`try_invert`]: Self::try_invert
+    pub fn invert<F>(&self, mut resolve: F) -> RtMap<V, K>
+    where
+        K: Clone,
+        V: Hash + Eq + Clone,
+        F: FnMut(&V, K, K) -> K,
+    {
+        let mut inverted = RtMap::with_capacity(self.inner.len());
+        self.inner.iter().for_each(|(k, cell)| {
+            let v = V::clone(&Self::borrow_cell(k, cell));
+            inverted.insert_resolved(v, k.clone(), &mut resolve);
+        });
+
+        inverted
+    }
+
+    /// Returns a map from each value to its key, or
+    /// [`BorrowFail::BorrowConflictImm`] if any value is being accessed
+    /// mutably.
+    ///
+    /// See [`invert`] for how duplicate values are resolved.
+    ///
+    /// [`invert`]: Self::invert
+    pub fn try_invert<F>(&self, mut resolve: F) -> Result<RtMap<V, K>, BorrowFail>
+    where
+        K: Clone,
+        V: Hash + Eq + Clone,
+        F: FnMut(&V, K, K) -> K,
+    {
+        let mut inverted = RtMap::with_capacity(self.inner.len());
+        self.inner.iter().try_for_each(|(k, cell)| {
+            let v = cell.try_borrow().map(|v| V::clone(&v))?;
+            inverted.insert_resolved(v, k.clone(), &mut resolve);
+            Ok(())
+        })?;
+
+        Ok(inverted)
+    }
+
+    /// Consumes the map, grouping its values by the key returned by `f`.
+    ///
+    /// Values within each group are in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert("apple", 3);
+    /// rt_map.insert("avocado", 5);
+    /// rt_map.insert("banana", 2);
+    ///
+    /// let mut groups = rt_map.group_by(|name, _count| name.chars().next());
+    ///
+    /// let mut a_counts = groups.remove(&Some('a')).unwrap();
+    /// a_counts.sort();
+    /// assert_eq!(vec![3, 5], a_counts);
+    /// assert_eq!(vec![2], *groups.borrow(&Some('b')));
+    /// ```
+    pub fn group_by<G, F>(self, mut f: F) -> RtMap<G, Vec<V>>
+    where
+        G: Hash + Eq,
+        F: FnMut(&K, &V) -> G,
+    {
+        let mut groups = RtMap::<G, Vec<V>>::new();
+        self.inner.into_iter().for_each(|(k, cell)| {
+            let v = cell.into_inner();
+            let g = f(&k, &v);
+            groups
+                .inner
+                .entry(g)
+                .or_insert_with(|| Cell::new(Vec::new()))
+                .get_mut()
+                .push(v);
+        });
+
+        groups
+    }
+
+    /// Returns the keys that are in either `self` or `other`, without
+    /// duplicates.
+    ///
+    /// Keys in `self` are returned first, followed by keys that are only in
+    /// `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut desired = RtMap::new();
+    /// desired.insert('a', 1);
+    /// desired.insert('b', 2);
+    ///
+    /// let mut actual = RtMap::new();
+    /// actual.insert('b', 2);
+    /// actual.insert('c', 3);
+    ///
+    /// let mut union = desired.keys_union(&actual).collect::<Vec<_>>();
+    /// union.sort();
+    /// assert_eq!(vec![&'a', &'b', &'c'], union);
+    ///
+    /// let to_create = desired.keys_difference(&actual).collect::<Vec<_>>();
+    /// let to_delete = actual.keys_difference(&desired).collect::<Vec<_>>();
+    /// let to_update = desired.keys_intersection(&actual).collect::<Vec<_>>();
+    /// assert_eq!(vec![&'a'], to_create);
+    /// assert_eq!(vec![&'c'], to_delete);
+    /// assert_eq!(vec![&'b'], to_update);
+    /// ```
+    pub fn keys_union<'a, W>(
+        &'a self,
+        other: &'a RtMap<K, W, S>,
+    ) -> impl Iterator<Item = &'a K> + 'a {
+        self.inner.keys().chain(other.keys_difference(self))
+    }
+
+    /// Returns the keys that are in both `self` and `other`.
+    pub fn keys_intersection<'a, W>(
+        &'a self,
+        other: &'a RtMap<K, W, S>,
+    ) -> impl Iterator<Item = &'a K> + 'a {
+        self.inner.keys().filter(|k| other.inner.contains_key(*k))
+    }
+
+    /// Returns the keys that are in `self` but not in `other`.
+    pub fn keys_difference<'a, W>(
+        &'a self,
+        other: &'a RtMap<K, W, S>,
+    ) -> impl Iterator<Item = &'a K> + 'a {
+        self.inner.keys().filter(|k| !other.inner.contains_key(*k))
+    }
+
+    /// Consumes both maps, returning a map with the entries of both.
+    ///
+    /// When a key is in both maps, `merge` is called with the key, the value
+    /// from `self`, and the value from `other`, and returns the value to
+    /// keep.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the union has more keys than `self`'s [maximum number of
+    /// entries].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut a = RtMap::new();
+    /// a.insert('a', 1);
+    /// a.insert('b', 2);
+    ///
+    /// let mut b = RtMap::new();
+    /// b.insert('b', 3);
+    /// b.insert('c', 4);
+    ///
+    /// let union = a.union_with(b, |_k, v_a, v_b| v_a + v_b);
+    ///
+    /// assert_eq!(1, *union.borrow(&'a'));
+    /// assert_eq!(5, *union.borrow(&'b'));
+    /// assert_eq!(4, *union.borrow(&'c'));
+    /// ```
+    ///
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn union_with<F>(mut self, other: RtMap<K, V, S>, mut merge: F) -> RtMap<K, V, S>
+    where
+        F: FnMut(&K, V, V) -> V,
+    {
+        self.inner.reserve(other.inner.len());
+        other.inner.into_iter().for_each(|(k, cell)| {
+            self.insert_resolved(k, cell.into_inner(), &mut merge);
+        });
+
+        self
+    }
+
+    /// Moves the entries of `other` into this map.
+    ///
+    /// When a key is in both maps, `resolve` is called with the key, this
+    /// map's value, and the value from `other`, and updates this map's value
+    /// in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a new key would exceed this map's [maximum number of
+    /// entries].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut a = RtMap::new();
+    /// a.insert('a', 1);
+    /// a.insert('b', 2);
+    ///
+    /// let mut b = RtMap::new();
+    /// b.insert('b', 3);
+    /// b.insert('c', 4);
+    ///
+    /// a.merge_from(b, |_k, v_a, v_b| *v_a += v_b);
+    ///
+    /// assert_eq!(1, *a.borrow(&'a'));
+    /// assert_eq!(5, *a.borrow(&'b'));
+    /// assert_eq!(4, *a.borrow(&'c'));
+    /// ```
+    ///
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn merge_from<F>(&mut self, other: RtMap<K, V, S>, mut resolve: F)
+    where
+        F: FnMut(&K, &mut V, V),
+    {
+        self.inner.reserve(other.inner.len());
+        other.inner.into_iter().for_each(|(k, cell)| {
+            let v = cell.into_inner();
+            match self.inner.get_mut(&k) {
+                Some(existing) => resolve(&k, existing.get_mut(), v),
+                None => {
+                    self.insert(k, v);
+                }
+            }
+        });
+    }
+
+    /// Returns the changes that turn this map into `other`.
+    ///
+    /// Values are compared with `PartialEq`, and the added and modified
+    /// values are cloned from `other`. See [`try_diff`] for a non-panicking
+    /// version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value that is compared or cloned is being accessed
+    /// mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let a = RtMap::from([('a', 1), ('b', 2)]);
+    /// let b = RtMap::from([('b', 3), ('c', 4)]);
+    ///
+    /// let change_set = a.diff(&b);
+    ///
+    /// assert_eq!(vec![('c', 4)], change_set.added);
+    /// assert_eq!(vec!['a'], change_set.removed);
+    /// assert_eq!(vec![('b', 3)], change_set.modified);
+    /// ```
+    ///
+    /// [`try_diff`]: Self::try_diff
+    pub fn diff(&self, other: &RtMap<K, V, S>) -> ChangeSet<K, V>
+    where
+        K: Clone,
+        V: PartialEq + Clone,
+    {
+        self.try_diff(other).unwrap_or_else(|_borrow_fail| {
+            panic!("Expected to diff the maps, but a value is being accessed mutably.")
+        })
+    }
+
+    /// Returns the changes that turn this map into `other`, or the first
+    /// [`BorrowFail`] if a value cannot be borrowed.
+    pub fn try_diff(&self, other: &RtMap<K, V, S>) -> Result<ChangeSet<K, V>, BorrowFail>
+    where
+        K: Clone,
+        V: PartialEq + Clone,
+    {
+        let mut change_set = ChangeSet::new();
+
+        for (k, cell) in self.inner.iter() {
+            match other.inner.get(k) {
+                Some(other_cell) => {
+                    let other_value = other_cell.try_borrow()?;
+                    if *cell.try_borrow()? != *other_value {
+                        change_set
+                            .modified
+                            .push((k.clone(), V::clone(&other_value)));
+                    }
+                }
+                None => change_set.removed.push(k.clone()),
+            }
+        }
+        for (k, other_cell) in other.inner.iter() {
+            if !self.inner.contains_key(k) {
+                let other_value = other_cell.try_borrow()?;
+                change_set.added.push((k.clone(), V::clone(&other_value)));
+            }
+        }
+
+        Ok(change_set)
+    }
+
+    /// Applies the changes from a [`diff`], removing, modifying, and adding
+    /// entries.
+    ///
+    /// Removals are applied first, so that they free up space for added
+    /// entries in maps with a [maximum number of entries]. See [`try_apply`]
+    /// for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the added keys would exceed the map's [maximum number of
+    /// entries]. This is checked before any change is applied, so the map is
+    /// unchanged when this panics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut a = RtMap::from([('a', 1), ('b', 2)]);
+    /// let b = RtMap::from([('b', 3), ('c', 4)]);
+    ///
+    /// let change_set = a.diff(&b);
+    /// a.apply(change_set);
+    ///
+    /// assert_eq!(b, a);
+    /// ```
+    ///
+    /// [`diff`]: Self::diff
+    /// [`try_apply`]: Self::try_apply
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn apply(&mut self, change_set: ChangeSet<K, V>) {
+        if let Err(capacity_exceeded) = self.try_apply(change_set) {
+            capacity_panic!(capacity_exceeded);
+        }
+    }
+
+    /// Applies the changes from a [`diff`], or leaves the map unchanged if
+    /// they would exceed the map's [maximum number of entries].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CapacityExceeded`] if the map would hold more than its
+    /// maximum number of entries after the changes are applied.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{ChangeSet, RtMap};
+    ///
+    /// let mut rt_map = RtMap::builder().with_max_entries(2).build();
+    /// rt_map.insert('a', 1);
+    ///
+    /// let mut change_set = ChangeSet::new();
+    /// change_set.modified.push(('a', 10));
+    /// change_set.added.extend([('b', 2), ('c', 3)]);
+    ///
+    /// assert!(rt_map.try_apply(change_set).is_err());
+    /// assert_eq!(1, *rt_map.borrow(&'a'));
+    /// ```
+    ///
+    /// [`diff`]: Self::diff
+    /// [maximum number of entries]: RtMapBuilder::with_max_entries
+    pub fn try_apply(&mut self, change_set: ChangeSet<K, V>) -> Result<(), CapacityExceeded> {
+        if let Some(max_entries) = self.max_entries {
+            let removed = change_set
+                .removed
+                .iter()
+                .filter(|k| self.inner.contains_key(*k))
+                .collect::<HashSet<&K>>();
+            let inserted = change_set
+                .modified
+                .iter()
+                .chain(&change_set.added)
+                .map(|(k, _v)| k)
+                .filter(|k| !self.inner.contains_key(*k) || removed.contains(k))
+                .collect::<HashSet<&K>>();
+            if self.inner.len() - removed.len() + inserted.len() > max_entries {
+                return Err(CapacityExceeded::new(max_entries));
+            }
+        }
+
+        let ChangeSet {
+            added,
+            removed,
+            modified,
+        } = change_set;
+
+        removed.iter().for_each(|k| {
+            self.inner.remove(k);
+        });
+        self.shrink_if_needed();
+
+        modified.into_iter().chain(added).for_each(|(k, v)| {
+            self.insert(k, v);
+        });
+
+        Ok(())
+    }
+
+    /// Inserts a value, or replaces the existing value with the result of
+    /// `resolve` if the key is already present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is a new key and the map is at its maximum number of
+    /// entries.
+    fn insert_resolved<F>(&mut self, k: K, v: V, resolve: &mut F)
+    where
+        F: FnMut(&K, V, V) -> V,
+    {
+        if let Err(capacity_exceeded) = self.check_max_entries(&k) {
+            capacity_panic!(capacity_exceeded);
+        }
+
+        match self.inner.entry(k) {
+            hashbrown::hash_map::Entry::Occupied(occupied_entry) => {
+                occupied_entry.replace_entry_with(|k, cell| {
+                    Some(Cell::new(resolve(k, cell.into_inner(), v)))
+                });
+            }
+            hashbrown::hash_map::Entry::Vacant(vacant_entry) => {
+                vacant_entry.insert(Cell::new(v));
+            }
+        }
+    }
+}
+
+/// Implements downcasting borrows for maps of boxed `Any` values.
+macro_rules! impl_borrow_downcast {
+    ($any:ty) => {
+        /// Borrows of type-erased values as their concrete types.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use std::any::Any;
+        ///
+        /// use rt_map::{ProjectionFail, RtMap};
+        #[doc = concat!(
+                            "let mut rt_map = RtMap::<&str, Box<", stringify!($any), ">>::new();"
+                        )]
+        /// rt_map.insert("count", Box::new(1u32));
+        /// rt_map.insert("name", Box::new(String::from("rt_map")));
+        ///
+        /// *rt_map.borrow_mut_downcast::<u32, _>("count") += 1;
+        ///
+        /// assert_eq!(2, *rt_map.borrow_downcast::<u32, _>("count"));
+        /// assert_eq!(
+        ///     Err(ProjectionFail::Mismatch),
+        ///     rt_map.try_borrow_downcast::<u32, _>("name").map(|count| *count)
+        /// );
+        /// ```
+        impl<K, S> RtMap<K, Box<$any>, S>
+        where
+            K: Hash + Eq,
+            S: BuildHasher,
+        {
+            /// Returns a reference to the value downcast to `T`.
+            ///
+            /// See [`try_borrow_downcast`] for a non-panicking version of this
+            /// function.
+            ///
+            /// # Panics
+            ///
+            /// * Panics if the resource doesn't exist.
+            /// * Panics if the resource is not a `T`.
+            /// * Panics if the resource is being accessed mutably.
+            ///
+            /// [`try_borrow_downcast`]: Self::try_borrow_downcast
+            #[track_caller]
+            pub fn borrow_downcast<T, Q>(&self, k: &Q) -> Ref<'_, T>
+            where
+                T: Any,
+                Q: ?Sized + Hash + Equivalent<K>,
+            {
+                let Some(cell) = self.inner.get(k) else {
+                    borrow_panic!(k);
+                };
+                let cell_ref = Self::borrow_cell(k, cell);
+                if !cell_ref.is::<T>() {
+                    projection_panic!(k, T);
+                }
+
+                Ref::new(cell_ref.map(|value| {
+                    value
+                        .downcast_ref::<T>()
+                        .expect("Value type was checked before downcasting.")
+                }))
+            }
+
+            /// Returns a reference to the value downcast to `T` if it exists,
+            /// is a `T`, and is not mutably borrowed.
+            pub fn try_borrow_downcast<T, Q>(&self, k: &Q) -> Result<Ref<'_, T>, ProjectionFail>
+            where
+                T: Any,
+                Q: ?Sized + Hash + Equivalent<K>,
+            {
+                let cell_ref = self
+                    .inner
+                    .get(k)
+                    .ok_or(BorrowFail::ValueNotFound)
+                    .and_then(Cell::try_borrow)?;
+                if !cell_ref.is::<T>() {
+                    return Err(ProjectionFail::Mismatch);
+                }
+
+                Ok(Ref::new(cell_ref.map(|value| {
+                    value
+                        .downcast_ref::<T>()
+                        .expect("Value type was checked before downcasting.")
+                })))
+            }
+
+            /// Returns a mutable reference to the value downcast to `T`.
+            ///
+            /// See [`try_borrow_mut_downcast`] for a non-panicking version of
+            /// this function.
+            ///
+            /// # Panics
+            ///
+            /// * Panics if the resource doesn't exist.
+            /// * Panics if the resource is not a `T`.
+            /// * Panics if the resource is already accessed.
+            ///
+            /// [`try_borrow_mut_downcast`]: Self::try_borrow_mut_downcast
+            #[track_caller]
+            pub fn borrow_mut_downcast<T, Q>(&self, k: &Q) -> RefMut<'_, T>
+            where
+                T: Any,
+                Q: ?Sized + Hash + Equivalent<K>,
+            {
+                let Some(cell) = self.inner.get(k) else {
+                    borrow_panic!(k);
+                };
+                let cell_ref_mut = Self::borrow_cell_mut(k, cell);
+                if !cell_ref_mut.is::<T>() {
+                    projection_panic!(k, T);
+                }
+
+                RefMut::new(cell_ref_mut.map(|value| {
+                    value
+                        .downcast_mut::<T>()
+                        .expect("Value type was checked before downcasting.")
+                }))
+            }
+
+            /// Returns a mutable reference to the value downcast to `T` if it
+            /// exists, is a `T`, and is not borrowed.
+            pub fn try_borrow_mut_downcast<T, Q>(
+                &self,
+                k: &Q,
+            ) -> Result<RefMut<'_, T>, ProjectionFail>
+            where
+                T: Any,
+                Q: ?Sized + Hash + Equivalent<K>,
+            {
+                let cell_ref_mut = self
+                    .inner
+                    .get(k)
+                    .ok_or(BorrowFail::ValueNotFound)
+                    .and_then(Cell::try_borrow_mut)?;
+                if !cell_ref_mut.is::<T>() {
+                    return Err(ProjectionFail::Mismatch);
+                }
+
+                Ok(RefMut::new(cell_ref_mut.map(|value| {
+                    value
+                        .downcast_mut::<T>()
+                        .expect("Value type was checked before downcasting.")
+                })))
+            }
+        }
+    };
+}
+
+impl_borrow_downcast!(dyn Any);
+impl_borrow_downcast!(dyn Any + Send + Sync);
+
+/// Consumes the map, yielding owned key-value pairs in arbitrary order.
+impl<K, V, S> IntoIterator for RtMap<K, V, S> {
+    type IntoIter = IntoIter<K, V>;
+    type Item = (K, V);
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.inner.into_iter())
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for RtMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher + Default,
+{
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        let mut rt_map = RtMap::default();
+        rt_map.extend(iter);
+        rt_map
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for RtMap<K, V>
+where
+    K: Hash + Eq,
+{
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let rt_map = RtMap::from([('a', 1), ('b', 2)]);
+    ///
+    /// assert_eq!(2, *rt_map.borrow(&'b'));
+    /// ```
+    fn from(entries: [(K, V); N]) -> Self {
+        let mut rt_map = RtMap::with_capacity(N);
+        rt_map.extend(entries);
+        rt_map
+    }
+}
+
+impl<K, V, S> From<std::collections::HashMap<K, V, S>> for RtMap<K, V>
+where
+    K: Hash + Eq,
+{
+    fn from(hash_map: std::collections::HashMap<K, V, S>) -> Self {
+        let mut rt_map = RtMap::with_capacity(hash_map.len());
+        rt_map.extend(hash_map);
+        rt_map
+    }
+}
+
+/// Clones the map with [`RtMap::try_clone`].
+///
+/// # Panics
+///
+/// Panics if any value is being accessed mutably.
+impl<K, V, S> Clone for RtMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    fn clone(&self) -> Self {
+        self.try_clone().unwrap_or_else(|_borrow_fail| {
+            panic!("Expected to clone the map, but a value is being accessed mutably.")
+        })
+    }
+}
+
+/// Compares the maps' entries, borrowing each value immutably.
+///
+/// The maps' limits and shrink policies are not compared.
+///
+/// # Panics
+///
+/// Panics if a value that is compared is being accessed mutably.
+impl<K, V, S> PartialEq for RtMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self.inner.iter().all(|(k, cell)| {
+                other.inner.get(k).is_some_and(|other_cell| {
+                    *Self::borrow_cell(k, cell) == *Self::borrow_cell(k, other_cell)
+                })
+            })
+    }
+}
+
+impl<K, V, S> Eq for RtMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+    V: Eq,
+{
+}
+
+/// Inserts each key-value pair with [`RtMap::insert`].
+///
+/// # Panics
+///
+/// Panics if inserting a pair would exceed the map's maximum number of
+/// entries.
+impl<K, V, S> Extend<(K, V)> for RtMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        iter.into_iter().for_each(|(k, v)| {
+            self.insert(k, v);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        any::Any,
+        collections::hash_map::DefaultHasher,
+        fmt,
+        hash::{BuildHasher, BuildHasherDefault},
+    };
+
+    use rt_ref::BorrowFail;
+
+    use super::RtMap;
+    use crate::{
+        CapacityExceeded, Equivalent, KeyedBorrowFail, OccupiedFail, Project, ProjectionFail,
+        ReplaceKeyFail, RetainAction, ShrinkPolicy,
+    };
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Res;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Value(u32);
+
+    #[derive(Debug)]
+    enum Shape {
+        Circle(f32),
+        Square,
+    }
+
+    impl Project<f32> for Shape {
+        fn project(&self) -> Option<&f32> {
+            match self {
+                Shape::Circle(radius) => Some(radius),
+                Shape::Square => None,
+            }
+        }
+
+        fn project_mut(&mut self) -> Option<&mut f32> {
+            match self {
+                Shape::Circle(radius) => Some(radius),
+                Shape::Square => None,
+            }
+        }
+    }
+
+    #[test]
+    fn insert() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Res);
+
+        assert!(rt_map.contains_key(&'a'));
+        assert!(!rt_map.contains_key(&'b'));
+    }
+
+    #[test]
+    fn lookups_accept_equivalent_keys() {
+        #[derive(Debug, Hash)]
+        struct KeyRef<'s>(&'s str, u32);
+
+        impl Equivalent<(String, u32)> for KeyRef<'_> {
+            fn equivalent(&self, key: &(String, u32)) -> bool {
+                self.0 == key.0 && self.1 == key.1
+            }
+        }
+
+        let mut rt_map = RtMap::new();
+        rt_map.insert((String::from("a"), 1), 1);
+
+        assert!(rt_map.contains_key(&KeyRef("a", 1)));
+        assert!(!rt_map.contains_key(&KeyRef("a", 2)));
+        *rt_map.borrow_mut(&KeyRef("a", 1)) += 1;
+        assert_eq!(2, *rt_map.borrow(&KeyRef("a", 1)));
+        assert_eq!(Some(2), rt_map.remove(&KeyRef("a", 1)));
+    }
+
+    #[test]
+    fn with_capacity_reserves_enough_capacity() {
+        let map: RtMap<i32, i32> = RtMap::with_capacity(100);
+        assert!(map.capacity() >= 100);
+    }
+
+    #[test]
+    fn builder_with_capacity_reserves_enough_capacity() {
+        let map = RtMap::<i32, i32>::builder().with_capacity(100).build();
+        assert!(map.capacity() >= 100);
+    }
+
+    #[test]
+    fn remove_shrinks_map_when_shrink_policy_fraction_is_met() {
+        let mut rt_map = RtMap::builder()
+            .with_shrink_policy(ShrinkPolicy::Fraction(4))
+            .build();
+        (0..100).for_each(|n| {
+            rt_map.insert(n, n);
+        });
+
+        (0..99).for_each(|n| {
+            rt_map.remove(&n);
+        });
+
+        assert!(rt_map.capacity() < 10);
+        assert_eq!(Some(&99), rt_map.get_mut(&99).map(|n| &*n));
+    }
+
+    #[test]
+    fn into_inner() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Res);
+        let inner_map = rt_map.into_inner();
+
+        assert!(inner_map.contains_key(&'a'));
+    }
+
+    #[test]
+    fn iter_mut_and_keys() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 0);
+        rt_map.insert('b', 1);
+
+        rt_map.iter_mut().for_each(|(_k, v)| *v += 1);
+
+        let a = rt_map.remove(&'a');
+        assert_eq!(Some(1), a);
+
+        assert_eq!(vec![&'b'], rt_map.keys().collect::<Vec<_>>());
+        assert_eq!(2, *rt_map.borrow(&'b'));
+    }
+
+    #[test]
+    fn clear_removes_all_entries() {
+        let mut rt_map = RtMap::builder()
+            .with_capacity(64)
+            .with_shrink_policy(ShrinkPolicy::Fraction(4))
+            .build();
+        rt_map.insert('a', 0);
+
+        rt_map.clear();
+
+        assert!(rt_map.is_empty());
+        assert!(rt_map.capacity() < 64);
+    }
+
+    #[test]
+    fn insert_unique_unchecked_inserts_value() {
+        let mut rt_map = RtMap::new();
+
+        let (k, v) = rt_map.insert_unique_unchecked('a', 1);
+        assert_eq!(('a', 1), (*k, *v));
+        *v = 2;
+
+        assert_eq!(2, *rt_map.borrow(&'a'));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "called with a key that already exists in the map")]
+    fn insert_unique_unchecked_existing_key_panics_in_debug() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        rt_map.insert_unique_unchecked('a', 2);
+    }
+
+    #[test]
+    fn is_empty_returns_true_when_map_does_not_contain_items() {
+        let rt_map = RtMap::<char, u32>::new();
+
+        assert!(rt_map.is_empty());
+    }
+
+    #[test]
+    fn is_empty_returns_false_when_map_contains_items() {
+        let mut rt_map = RtMap::new();
+
+        rt_map.insert('a', 0);
+
+        assert!(!rt_map.is_empty());
+    }
+
+    #[test]
+    fn entry_insert_value() {
+        let mut rt_map = RtMap::new();
+
+        let ref_mut = rt_map.entry('a').or_insert(1);
+
+        assert_eq!(1, *ref_mut);
+        drop(ref_mut);
+
+        let ref_mut = rt_map.entry('a').or_insert(2);
+
+        assert_eq!(1, *ref_mut);
+        drop(ref_mut);
+
+        rt_map.remove(&'a');
+
+        let ref_mut = rt_map.entry('a').or_insert_with(|| 3);
+
+        assert_eq!(3, *ref_mut);
+        drop(ref_mut);
+    }
+
+    #[test]
+    fn entry_ref_insert_value() {
+        let mut rt_map = RtMap::<String, u32>::new();
+
+        let ref_mut = rt_map.entry_ref("a").or_insert(1);
+
+        assert_eq!(1, *ref_mut);
+        drop(ref_mut);
+
+        let ref_mut = rt_map.entry_ref("a").or_insert(2);
+
+        assert_eq!(1, *ref_mut);
+        drop(ref_mut);
+
+        rt_map.remove("a");
+
+        let ref_mut = rt_map.entry_ref("a").or_insert_with(|| 3);
+
+        assert_eq!(3, *ref_mut);
+        drop(ref_mut);
+    }
+
+    #[test]
+    fn get_mut_returns_mutable_reference_to_value() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Value(1));
+
+        let value = rt_map.get_mut(&'a');
+
+        assert!(value.is_some());
+
+        if let Some(value) = value {
+            *value = Value(2);
+        }
+
+        let value = rt_map.get_mut(&'a').map(|value| value.0);
+
+        assert_eq!(Some(2), value);
+    }
+
+    #[test]
+    #[should_panic(expected = "but it was already borrowed")]
+    fn read_write_fails() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Res);
+
+        let _read = rt_map.borrow(&'a');
+        let _write = rt_map.borrow_mut(&'a');
+    }
+
+    #[test]
+    #[should_panic(expected = "but it was already borrowed mutably")]
+    fn write_read_fails() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Res);
+
+        let _write = rt_map.borrow_mut(&'a');
+        let _read = rt_map.borrow(&'a');
+    }
+
+    #[test]
+    fn remove_insert() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Res);
+
+        assert!(rt_map.contains_key(&'a'));
+
+        rt_map.remove(&'a').unwrap();
+
+        assert!(!rt_map.contains_key(&'a'));
+
+        rt_map.insert('a', Res);
+
+        assert!(rt_map.contains_key(&'a'));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow a value with a `char` key, but the key does not exist."
+    )]
+    fn borrow_before_insert_panics() {
+        let rt_map = RtMap::<char, i32>::new();
+
+        rt_map.borrow(&'a');
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow a value with a `char` key, but the key does not exist."
+    )]
+    fn borrow_mut_before_insert_panics() {
+        let rt_map = RtMap::<char, i32>::new();
+
+        rt_map.borrow_mut(&'a');
+    }
+
+    #[test]
+    fn borrow_mut_try_borrow_returns_borrow_conflict_imm() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Res);
+
+        let _res = rt_map.borrow_mut(&'a');
+
+        assert_eq!(Err(BorrowFail::BorrowConflictImm), rt_map.try_borrow(&'a'));
+    }
+
+    #[test]
+    fn borrow_try_borrow_mut_returns_borrow_conflict_mut() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Res);
+
+        let _res = rt_map.borrow(&'a');
+
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictMut),
+            rt_map.try_borrow_mut(&'a')
+        );
+    }
+
+    #[test]
+    fn borrow_mut_borrow_mut_returns_borrow_conflict_mut() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Res);
+
+        let _res = rt_map.borrow_mut(&'a');
+
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictMut),
+            rt_map.try_borrow_mut(&'a')
+        );
+    }
+
+    #[test]
+    fn try_borrow_before_insert_returns_value_not_found() {
+        let rt_map = RtMap::<char, Res>::new();
+
+        assert_eq!(Err(BorrowFail::ValueNotFound), rt_map.try_borrow(&'a'));
+    }
+
+    #[test]
+    fn try_borrow_mut_before_insert_returns_value_not_found() {
+        let rt_map = RtMap::<char, Res>::new();
+
+        assert_eq!(Err(BorrowFail::ValueNotFound), rt_map.try_borrow_mut(&'a'));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow a value with a `char` key, but the key does not exist."
+    )]
+    fn borrow_before_insert_panics_value_not_found() {
+        let rt_map = RtMap::<char, Res>::new();
+
+        rt_map.borrow(&'a');
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow a value with a `char` key, but the key does not exist."
+    )]
+    fn borrow_mut_before_insert_panics_value_not_found() {
+        let rt_map = RtMap::<char, Res>::new();
+
+        rt_map.borrow_mut(&'a');
+    }
+
+    #[test]
+    fn borrow_mut_downcast_updates_value() {
+        let mut rt_map = RtMap::<char, Box<dyn Any + Send + Sync>>::new();
+        rt_map.insert('a', Box::new(1u32));
+
+        *rt_map.borrow_mut_downcast::<u32, _>(&'a') += 1;
+
+        assert_eq!(2, *rt_map.borrow_downcast::<u32, _>(&'a'));
+    }
+
+    #[test]
+    fn try_borrow_mut_downcast_returns_mismatch_for_different_type() {
+        let mut rt_map = RtMap::<char, Box<dyn Any>>::new();
+        rt_map.insert('a', Box::new(1u32));
+
+        assert_eq!(
+            Err(ProjectionFail::Mismatch),
+            rt_map.try_borrow_mut_downcast::<u64, _>(&'a')
+        );
+    }
+
+    #[test]
+    fn try_borrow_downcast_returns_borrow_conflict_imm() {
+        let mut rt_map = RtMap::<char, Box<dyn Any>>::new();
+        rt_map.insert('a', Box::new(1u32));
+
+        let _a = rt_map.borrow_mut_downcast::<u32, _>(&'a');
+
+        assert_eq!(
+            Err(ProjectionFail::Borrow(BorrowFail::BorrowConflictImm)),
+            rt_map.try_borrow_downcast::<u32, _>(&'a')
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "with a `char` key as `u64`, but it is a different type.")]
+    fn borrow_downcast_different_type_panics() {
+        let mut rt_map = RtMap::<char, Box<dyn Any>>::new();
+        rt_map.insert('a', Box::new(1u32));
+
+        rt_map.borrow_downcast::<u64, _>(&'a');
+    }
+
+    #[test]
+    fn try_borrow_mut_variant_returns_mismatch_for_different_variant() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Shape::Square);
+
+        assert_eq!(
+            Err(ProjectionFail::Mismatch),
+            rt_map.try_borrow_mut_variant::<f32, _>(&'a')
+        );
+    }
+
+    #[test]
+    fn try_borrow_variant_returns_borrow_conflict_imm() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Shape::Circle(1.0));
+
+        let _radius = rt_map.borrow_mut_variant::<f32, _>(&'a');
+
+        assert_eq!(
+            Err(ProjectionFail::Borrow(BorrowFail::BorrowConflictImm)),
+            rt_map.try_borrow_variant::<f32, _>(&'a')
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "with a `char` key as `f32`, but it is a different type.")]
+    fn borrow_variant_different_variant_panics() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Shape::Square);
+
+        rt_map.borrow_variant::<f32, _>(&'a');
+    }
+
+    #[test]
+    fn contains_value_returns_whether_any_value_is_equal() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        assert!(rt_map.contains_value(&2));
+        assert!(!rt_map.contains_value(&3));
+    }
+
+    #[test]
+    fn try_contains_value_returns_borrow_conflict_imm() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let _a = rt_map.borrow_mut(&'a');
+
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictImm),
+            rt_map.try_contains_value(&1)
+        );
+    }
+
+    #[test]
+    fn try_find_keys_by_value_returns_borrow_conflict_imm() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let _b = rt_map.borrow_mut(&'b');
+
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictImm),
+            rt_map.try_find_keys_by_value(|v| *v == 1)
+        );
+    }
+
+    #[test]
+    fn invert_maps_values_to_keys() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let inverted = rt_map.invert(|_v, _k_existing, _k| unreachable!());
+
+        assert_eq!('a', *inverted.borrow(&1));
+        assert_eq!('b', *inverted.borrow(&2));
+    }
+
+    #[test]
+    fn invert_resolves_duplicate_values() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 1);
+        rt_map.insert('c', 1);
+
+        let inverted = rt_map.invert(|_v, k_existing, k| k_existing.max(k));
+
+        assert_eq!(1, inverted.len());
+        assert_eq!('c', *inverted.borrow(&1));
+    }
+
+    #[test]
+    fn try_invert_returns_borrow_conflict_imm() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let _a = rt_map.borrow_mut(&'a');
+
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictImm),
+            rt_map
+                .try_invert(|_v, k_existing, _k| k_existing)
+                .map(|_| ())
+        );
+    }
+
+    #[test]
+    fn group_by_groups_values_by_key() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert(1, 'a');
+        rt_map.insert(2, 'b');
+        rt_map.insert(3, 'c');
+
+        let mut groups = rt_map.group_by(|k, _v| k % 2 == 0);
+
+        assert_eq!(2, groups.len());
+        let mut odd = groups.remove(&false).unwrap();
+        odd.sort();
+        assert_eq!(vec!['a', 'c'], odd);
+        assert_eq!(vec!['b'], *groups.borrow(&true));
+    }
+
+    #[test]
+    fn keys_union_returns_each_key_once() {
+        let mut a = RtMap::new();
+        a.insert('a', 1);
+        a.insert('b', 2);
+        let mut b = RtMap::new();
+        b.insert('b', "b");
+        b.insert('c', "c");
+
+        let mut union = a.keys_union(&b).copied().collect::<Vec<_>>();
+        union.sort();
+
+        assert_eq!(vec!['a', 'b', 'c'], union);
+    }
+
+    #[test]
+    fn keys_intersection_and_difference_partition_keys() {
+        let mut a = RtMap::new();
+        a.insert('a', 1);
+        a.insert('b', 2);
+        let mut b = RtMap::new();
+        b.insert('b', "b");
+
+        let intersection = a.keys_intersection(&b).copied().collect::<Vec<_>>();
+        let difference = a.keys_difference(&b).copied().collect::<Vec<_>>();
+
+        assert_eq!(vec!['b'], intersection);
+        assert_eq!(vec!['a'], difference);
+    }
+
+    #[test]
+    fn union_with_merges_values_for_shared_keys() {
+        let mut a = RtMap::new();
+        a.insert('a', 1);
+        a.insert('b', 2);
+        let mut b = RtMap::new();
+        b.insert('b', 10);
+
+        let union = a.union_with(b, |_k, v_a, v_b| v_a * v_b);
+
+        assert_eq!(2, union.len());
+        assert_eq!(20, *union.borrow(&'b'));
+    }
+
+    #[test]
+    fn checked_insert_returns_capacity_exceeded_for_new_key_at_max_entries() {
+        let mut rt_map = RtMap::builder().with_max_entries(2).build();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        assert_eq!(Ok(Some(1)), rt_map.checked_insert('a', 3));
+        assert_eq!(Err(CapacityExceeded::new(2)), rt_map.checked_insert('c', 4));
+        assert_eq!(2, rt_map.len());
+    }
+
+    #[test]
+    fn checked_entry_returns_capacity_exceeded_for_new_key_at_max_entries() {
+        let mut rt_map = RtMap::builder().with_max_entries(1).build();
+        rt_map.insert('a', 1);
+
+        *rt_map.checked_entry('a').unwrap().or_insert(0) += 1;
+
+        assert_eq!(2, *rt_map.borrow(&'a'));
+        assert!(rt_map.checked_entry('b').is_err());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to insert a new key, but the map is at its maximum of 1 entries."
+    )]
+    fn insert_new_key_at_max_entries_panics() {
+        let mut rt_map = RtMap::builder().with_max_entries(1).build();
+        rt_map.insert('a', 1);
+
+        rt_map.insert('b', 2);
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn borrow_stats_counts_try_borrow_outcomes() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let a = rt_map.try_borrow_mut(&'a');
+        let _a_conflict = rt_map.try_borrow(&'a');
+        let _b = rt_map.try_borrow_mut(&'b');
+        drop(a);
+
+        assert_eq!(
+            crate::BorrowStats {
+                hits: 1,
+                not_found: 1,
+                conflicts: 1,
+            },
+            rt_map.borrow_stats()
+        );
+
+        rt_map.reset_borrow_stats();
+
+        assert_eq!(crate::BorrowStats::default(), rt_map.borrow_stats());
+    }
+
+    #[test]
+    fn iter_sorted_returns_entries_in_key_order() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert(3, 'c');
+        rt_map.insert(1, 'a');
+        rt_map.insert(2, 'b');
+
+        let entries = rt_map
+            .iter_sorted()
+            .map(|(k, v)| (*k, *v))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![(1, 'a'), (2, 'b'), (3, 'c')], entries);
+        assert_eq!(vec![&1, &2, &3], rt_map.keys_sorted());
+    }
+
+    #[test]
+    fn drain_into_moves_entries_into_non_empty_map() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+        let mut other = RtMap::new();
+        other.insert('b', 0);
+        other.insert('c', 3);
+
+        rt_map.drain_into(&mut other);
+
+        assert!(rt_map.is_empty());
+        assert_eq!(3, other.len());
+        assert_eq!(2, *other.borrow(&'b'));
+    }
+
+    #[test]
+    fn drain_into_empty_map_reuses_allocation() {
+        let mut rt_map = RtMap::with_capacity(100);
+        rt_map.insert('a', 1);
+        let capacity = rt_map.capacity();
+        let mut other = RtMap::new();
+
+        rt_map.drain_into(&mut other);
+
+        assert_eq!(capacity, other.capacity());
+        assert_eq!(1, *other.borrow(&'a'));
+    }
+
+    #[test]
+    #[should_panic(expected = "but the map is at its maximum of 1 entries.")]
+    fn drain_into_empty_bounded_map_panics_when_over_max_entries() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+        let mut other = RtMap::builder().with_max_entries(1).build();
+
+        rt_map.drain_into(&mut other);
+    }
+
+    #[test]
+    fn drain_into_if_moves_matching_entries() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+        rt_map.insert('c', 3);
+        let mut other = RtMap::new();
+
+        rt_map.drain_into_if(&mut other, |_k, v| *v % 2 == 1);
+
+        assert_eq!(vec![&'b'], rt_map.keys_sorted());
+        assert_eq!(vec![&'a', &'c'], other.keys_sorted());
+    }
+
+    #[test]
+    fn pop_any_removes_every_entry() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let mut entries = vec![rt_map.pop_any().unwrap(), rt_map.pop_any().unwrap()];
+        entries.sort();
+
+        assert_eq!(vec![('a', 1), ('b', 2)], entries);
+        assert!(rt_map.is_empty());
+        assert_eq!(None, rt_map.pop_any());
+    }
+
+    #[test]
+    fn pop_first_removes_smallest_key() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert(3, 'c');
+        rt_map.insert(1, 'a');
+        rt_map.insert(2, 'b');
+
+        assert_eq!(Some((1, 'a')), rt_map.pop_first());
+        assert_eq!(vec![&2, &3], rt_map.keys_sorted());
+    }
+
+    #[test]
+    fn get_key_value_returns_stored_key() {
+        #[derive(Debug, Eq)]
+        struct Name(&'static str);
+
+        impl PartialEq for Name {
+            fn eq(&self, other: &Self) -> bool {
+                self.0.eq_ignore_ascii_case(other.0)
+            }
+        }
+
+        impl std::hash::Hash for Name {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.0.to_ascii_lowercase().hash(state);
+            }
+        }
+
+        let mut rt_map = RtMap::new();
+        rt_map.insert(Name("Alice"), 1);
+
+        let (k, v) = rt_map.get_key_value(&Name("alice")).unwrap();
+
+        assert_eq!("Alice", k.0);
+        assert_eq!(1, *v);
+    }
+
+    #[test]
+    fn try_get_key_value_returns_borrow_conflict_imm() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let _a = rt_map.borrow_mut(&'a');
+
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictImm),
+            rt_map.try_get_key_value(&'a').map(|(k, v)| (*k, *v))
+        );
+    }
+
+    #[test]
+    fn get_many_borrows_same_key_multiple_times() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let [a_0, a_1] = rt_map.get_many([&'a', &'a']);
+
+        assert_eq!(Some(1), a_0.map(|a| *a));
+        assert_eq!(Some(1), a_1.map(|a| *a));
+    }
+
+    #[test]
+    fn try_get_many_returns_each_borrow_fail() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let _b = rt_map.borrow_mut(&'b');
+        let [a, b, c] = rt_map
+            .try_get_many([&'a', &'b', &'c'])
+            .map(|v| v.map(|v| *v));
+
+        assert_eq!(Ok(1), a);
+        assert_eq!(Err(BorrowFail::BorrowConflictImm), b);
+        assert_eq!(Err(BorrowFail::ValueNotFound), c);
+    }
+
+    #[test]
+    fn borrow_mut_or_default_inserts_default_for_missing_key() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        *rt_map.borrow_mut_or_default('a') += 1;
+        *rt_map.borrow_mut_or_default('b') += 1;
+
+        assert_eq!(2, *rt_map.borrow(&'a'));
+        assert_eq!(1, *rt_map.borrow(&'b'));
+    }
+
+    #[test]
+    fn replace_key_moves_value_to_new_key() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        assert_eq!(Ok('a'), rt_map.replace_key(&'a', 'b'));
+        assert!(!rt_map.contains_key(&'a'));
+        assert_eq!(1, *rt_map.borrow(&'b'));
+    }
+
+    #[test]
+    fn replace_key_returns_key_not_found_for_missing_key() {
+        let mut rt_map = RtMap::<char, u32>::new();
+
+        assert_eq!(
+            Err(ReplaceKeyFail::KeyNotFound),
+            rt_map.replace_key(&'a', 'b')
+        );
+    }
+
+    #[test]
+    fn replace_key_returns_key_exists_when_new_key_exists() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        assert_eq!(
+            Err(ReplaceKeyFail::KeyExists),
+            rt_map.replace_key(&'a', 'b')
+        );
+        assert_eq!(1, *rt_map.borrow(&'a'));
+        assert_eq!(2, *rt_map.borrow(&'b'));
+    }
+
+    #[test]
+    fn iter_available_mut_skips_borrowed_entries() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+        rt_map.insert('c', 3);
+
+        let _a = rt_map.borrow(&'a');
+        let mut keys = rt_map
+            .iter_available_mut()
+            .map(|(k, mut v)| {
+                *v += 10;
+                *k
+            })
+            .collect::<Vec<_>>();
+        keys.sort();
+
+        assert_eq!(vec!['b', 'c'], keys);
+        assert_eq!(12, *rt_map.borrow(&'b'));
+    }
+
+    #[test]
+    fn try_fold_borrow_stops_at_error_from_f() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let result = rt_map.try_fold_borrow(0, |sum, k, v| {
+            if *k == 'b' {
+                Err(ProjectionFail::Mismatch)
+            } else {
+                Ok(sum + v)
+            }
+        });
+
+        assert_eq!(Err(ProjectionFail::Mismatch), result);
+    }
+
+    #[test]
+    fn select_mut_returns_matching_values_and_conflicts() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+        rt_map.insert('c', 3);
+
+        let _c = rt_map.borrow(&'c');
+        let mut selected = rt_map.select_mut(|_k, v| *v >= 2);
+        selected.sort_by_key(|(k, _result)| **k);
+
+        assert_eq!(2, selected.len());
+        let (b, b_result) = &mut selected[0];
+        assert_eq!('b', **b);
+        **b_result.as_mut().unwrap() += 10;
+        let (c, c_result) = &selected[1];
+        assert_eq!('c', **c);
+        assert_eq!(
+            Some(BorrowFail::BorrowConflictMut),
+            c_result.as_ref().err().copied()
+        );
+        drop(selected);
+
+        assert_eq!(12, *rt_map.borrow(&'b'));
+    }
+
+    #[test]
+    fn entry_many_or_insert_with_inserts_missing_keys() {
+        let mut rt_map = RtMap::<String, usize>::new();
+        rt_map.insert(String::from("a"), 1);
+
+        let [a, bb, ccc] = rt_map.entry_many_or_insert_with(["a", "bb", "ccc"], str::len);
+        *a += 10;
+        *bb += 10;
+        *ccc += 10;
+
+        assert_eq!(11, *rt_map.borrow("a"));
+        assert_eq!(12, *rt_map.borrow("bb"));
+        assert_eq!(13, *rt_map.borrow("ccc"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected distinct keys")]
+    fn entry_many_or_insert_with_duplicate_keys_panics() {
+        let mut rt_map = RtMap::<String, u32>::new();
+
+        rt_map.entry_many_or_insert_with(["a", "a"], |_k| 0);
+    }
+
+    #[test]
+    fn entry_many_or_insert_with_duplicate_keys_does_not_insert() {
+        let mut rt_map = RtMap::<String, u32>::new();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            rt_map.entry_many_or_insert_with(["a", "b", "b"], |_k| 0);
+        }));
+
+        assert!(result.is_err());
+        assert!(rt_map.is_empty());
+    }
+
+    #[test]
+    fn entry_many_or_insert_with_over_max_entries_does_not_insert() {
+        let mut rt_map = RtMap::<String, u32>::builder().with_max_entries(2).build();
+        rt_map.insert(String::from("a"), 1);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            rt_map.entry_many_or_insert_with(["a", "b", "c"], |_k| 0);
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(1, rt_map.len());
+        assert!(!rt_map.contains_key("b"));
+    }
+
+    #[test]
+    fn entry_many_or_insert_with_grows_map() {
+        let mut rt_map = RtMap::<String, usize>::new();
+        rt_map.insert(String::from("a"), 1);
+        let keys: [&str; 8] = ["a", "b", "c", "d", "e", "f", "g", "h"];
+
+        let values = rt_map.entry_many_or_insert_with(keys, str::len);
+        values.into_iter().for_each(|v| *v += 10);
+
+        assert_eq!(8, rt_map.len());
+        assert_eq!(11, *rt_map.borrow("a"));
+        assert_eq!(11, *rt_map.borrow("h"));
+    }
+
+    #[test]
+    fn get_or_insert_mut_inserts_default_when_missing() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        *rt_map.get_or_insert_mut('a', 10) += 1;
+        *rt_map.get_or_insert_mut('b', 10) += 1;
+
+        assert_eq!(2, *rt_map.borrow(&'a'));
+        assert_eq!(11, *rt_map.borrow(&'b'));
+    }
+
+    #[test]
+    #[should_panic(expected = "maximum of 1 entries")]
+    fn get_or_insert_mut_panics_when_max_entries_exceeded() {
+        let mut rt_map = RtMap::builder().with_max_entries(1).build();
+        rt_map.insert('a', 1);
+
+        rt_map.get_or_insert_mut('b', 2);
+    }
+
+    #[test]
+    fn values_mut_returns_every_value() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        rt_map.values_mut().for_each(|v| *v *= 10);
+
+        assert_eq!(10, *rt_map.borrow(&'a'));
+        assert_eq!(20, *rt_map.borrow(&'b'));
+    }
+
+    #[test]
+    fn borrow_map_returns_trait_object() {
+        trait Named {
+            fn name(&self) -> &'static str;
+        }
+
+        impl Named for Res {
+            fn name(&self) -> &'static str {
+                "res"
+            }
+        }
+
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', Res);
+
+        let named = rt_map.borrow_map(&'a', |res| res as &dyn Named);
+
+        assert_eq!("res", named.name());
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictMut),
+            rt_map
+                .try_borrow_mut_map(&'a', |res| res as &mut dyn Named)
+                .map(|_| ())
+        );
+    }
+
+    #[test]
+    fn try_borrow_mut_map_returns_value_not_found() {
+        let rt_map = RtMap::<char, u32>::new();
+
+        assert_eq!(
+            Err(BorrowFail::ValueNotFound),
+            rt_map
+                .try_borrow_mut_map(&'a', |v| v as &mut dyn fmt::Debug)
+                .map(|_| ())
+        );
+    }
+
+    #[test]
+    fn retain_until_stop_keeps_remaining_entries() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+        rt_map.insert('c', 3);
+
+        let mut visited = 0;
+        rt_map.retain_until(|_k, _v| {
+            visited += 1;
+            if visited == 1 {
+                RetainAction::Remove
+            } else {
+                RetainAction::Stop
+            }
+        });
+
+        assert_eq!(2, visited);
+        assert_eq!(2, rt_map.len());
+    }
+
+    #[test]
+    fn iter_borrows_every_value() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let mut entries = rt_map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+        entries.sort_unstable();
+
+        assert_eq!(vec![('a', 1), ('b', 2)], entries);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iter_panics_when_value_is_mutably_borrowed() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let _a = rt_map.borrow_mut(&'a');
+
+        rt_map.iter().for_each(drop);
+    }
+
+    #[test]
+    fn try_iter_returns_borrow_fail_for_mutably_borrowed_values() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let _b = rt_map.borrow_mut(&'b');
+
+        let mut entries = rt_map
+            .try_iter()
+            .map(|(k, v)| (*k, v.map(|v| *v)))
+            .collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(k, _v)| *k);
+
+        assert_eq!(
+            vec![('a', Ok(1)), ('b', Err(BorrowFail::BorrowConflictImm))],
+            entries
+        );
+    }
+
+    #[test]
+    fn try_iter_mut_returns_borrow_fail_for_borrowed_values() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let b = rt_map.borrow(&'b');
+        let mut entries = rt_map
+            .try_iter_mut()
+            .map(|(k, v)| {
+                (
+                    *k,
+                    v.map(|mut v| {
+                        *v += 10;
+                        *v
+                    }),
+                )
+            })
+            .collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(k, _v)| *k);
+        drop(b);
+
+        assert_eq!(
+            vec![('a', Ok(11)), ('b', Err(BorrowFail::BorrowConflictMut))],
+            entries
+        );
+        assert_eq!(11, *rt_map.borrow(&'a'));
+    }
+
+    #[test]
+    fn values_borrows_every_value() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let mut values = rt_map.values().map(|v| *v).collect::<Vec<_>>();
+        values.sort_unstable();
+
+        assert_eq!(vec![1, 2], values);
+    }
+
+    #[test]
+    fn try_values_returns_borrow_fail_for_mutably_borrowed_values() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let _a = rt_map.borrow_mut(&'a');
+        let values = rt_map
+            .try_values()
+            .map(|v| v.map(|v| *v))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![Err(BorrowFail::BorrowConflictImm)], values);
+    }
+
+    #[test]
+    fn drain_removes_entries_when_dropped_early() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        assert!(rt_map.drain().next().is_some());
+
+        assert!(rt_map.is_empty());
+    }
+
+    #[test]
+    fn retain_keeps_matching_entries() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+        rt_map.insert('c', 3);
+
+        rt_map.retain(|k, v| {
+            *v += 10;
+            *k != 'b'
+        });
+
+        assert_eq!(11, *rt_map.borrow(&'a'));
+        assert!(!rt_map.contains_key(&'b'));
+        assert_eq!(13, *rt_map.borrow(&'c'));
+    }
+
+    #[test]
+    fn extract_if_removes_matching_entries() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+        rt_map.insert('c', 3);
+
+        let threshold = 2;
+        let mut removed = rt_map
+            .extract_if(|_k, v| *v >= threshold)
+            .collect::<Vec<_>>();
+        removed.sort_unstable();
+
+        assert_eq!(vec![('b', 2), ('c', 3)], removed);
+        assert_eq!(1, rt_map.len());
+    }
+
+    #[test]
+    fn drain_and_extract_if_do_not_apply_shrink_policy() {
+        let mut rt_map = RtMap::builder()
+            .with_shrink_policy(ShrinkPolicy::Fraction(4))
+            .build();
+        (0..100).for_each(|n| {
+            rt_map.insert(n, n);
+        });
+
+        rt_map.extract_if(|k, _v| *k != 0).for_each(drop);
+        assert!(rt_map.capacity() >= 10);
+
+        rt_map.drain().for_each(drop);
+        assert!(rt_map.capacity() >= 10);
+    }
+
+    #[test]
+    fn extend_inserts_every_pair() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 0);
+
+        rt_map.extend([('a', 1), ('b', 2)]);
+
+        assert_eq!(1, *rt_map.borrow(&'a'));
+        assert_eq!(2, *rt_map.borrow(&'b'));
+    }
+
+    #[test]
+    fn from_iter_collects_pairs() {
+        let rt_map = [('a', 1), ('b', 2)].into_iter().collect::<RtMap<_, _>>();
+
+        assert_eq!(2, rt_map.len());
+        assert_eq!(1, *rt_map.borrow(&'a'));
+    }
+
+    #[test]
+    fn from_array_inserts_pairs() {
+        let rt_map = RtMap::from([('a', 1), ('b', 2)]);
+
+        assert_eq!(2, rt_map.len());
+        assert_eq!(2, *rt_map.borrow(&'b'));
+    }
+
+    #[test]
+    fn into_iter_yields_owned_pairs() {
+        let rt_map = RtMap::from([('a', 1), ('b', 2)]);
+
+        let mut entries = rt_map.into_iter().collect::<Vec<_>>();
+        entries.sort_unstable();
+
+        assert_eq!(vec![('a', 1), ('b', 2)], entries);
+    }
+
+    #[test]
+    fn from_hash_map_and_into_hash_map_round_trip() {
+        let hash_map = std::collections::HashMap::from([('a', 1), ('b', 2)]);
+
+        let rt_map = RtMap::from(hash_map.clone());
+        assert_eq!(2, *rt_map.borrow(&'b'));
+
+        assert_eq!(hash_map, rt_map.into_hash_map());
+    }
+
+    #[test]
+    fn clone_copies_values_and_limits() {
+        let mut rt_map = RtMap::builder().with_max_entries(2).build();
+        rt_map.insert('a', 1);
+
+        let _a = rt_map.borrow(&'a');
+        let mut rt_map_clone = rt_map.clone();
+        *rt_map_clone.borrow_mut(&'a') += 1;
+
+        assert_eq!(1, *rt_map.borrow(&'a'));
+        assert_eq!(2, *rt_map_clone.borrow(&'a'));
+        assert_eq!(Some(2), rt_map_clone.max_entries());
+        rt_map_clone.insert('b', 2);
+        assert_eq!(
+            Err(CapacityExceeded::new(2)),
+            rt_map_clone.checked_insert('c', 3)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected to clone the map, but a value is being accessed mutably.")]
+    fn clone_panics_when_value_is_mutably_borrowed() {
+        let rt_map = RtMap::from([('a', 1)]);
+
+        let _a = rt_map.borrow_mut(&'a');
+
+        let _rt_map_clone = rt_map.clone();
+    }
+
+    #[test]
+    fn eq_compares_entries() {
+        let rt_map = RtMap::from([('a', 1), ('b', 2)]);
+
+        assert_eq!(RtMap::from([('b', 2), ('a', 1)]), rt_map);
+        assert_ne!(RtMap::from([('a', 1), ('b', 3)]), rt_map);
+        assert_ne!(RtMap::from([('a', 1)]), rt_map);
     }
 
     #[test]
-    fn is_empty_returns_false_when_map_contains_items() {
+    fn with_hasher_uses_given_hash_builder() {
+        type Hasher = BuildHasherDefault<DefaultHasher>;
+
+        let mut rt_map = RtMap::<char, u32, Hasher>::with_capacity_and_hasher(4, Hasher::default());
+        rt_map.insert('a', 1);
+        *rt_map.entry('b').or_insert(1) += 1;
+
+        let rt_map_clone = rt_map.clone();
+        assert_eq!(rt_map, rt_map_clone);
+        assert_eq!(2, *rt_map_clone.borrow(&'b'));
+        assert_eq!(1, rt_map.scope(|scope| *scope.borrow(&'a')));
+    }
+
+    #[test]
+    fn try_insert_does_not_overwrite_existing_value() {
         let mut rt_map = RtMap::new();
 
-        rt_map.insert('a', 0);
+        *rt_map.try_insert('a', 1).unwrap() += 1;
 
-        assert!(!rt_map.is_empty());
+        assert_eq!(
+            Err(OccupiedFail { key: 'a', value: 3 }),
+            rt_map.try_insert('a', 3)
+        );
+        assert_eq!(2, *rt_map.borrow(&'a'));
     }
 
     #[test]
-    fn entry_insert_value() {
+    fn try_borrow_many_mut_borrows_all_or_none() {
         let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
 
-        let ref_mut = rt_map.entry('a').or_insert(1);
+        let _b = rt_map.borrow(&'b');
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictMut),
+            rt_map.try_borrow_many_mut([&'a', &'b']).map(|_| ())
+        );
+        assert!(rt_map.try_borrow_mut(&'a').is_ok());
+        assert_eq!(
+            Err(BorrowFail::ValueNotFound),
+            rt_map.try_borrow_many_mut([&'a', &'c']).map(|_| ())
+        );
+    }
 
-        assert_eq!(1, *ref_mut);
-        drop(ref_mut);
+    #[test]
+    #[should_panic(
+        expected = "Expected distinct keys, but `borrow_many_mut` was passed a key more than once."
+    )]
+    fn borrow_many_mut_duplicate_keys_panics() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
 
-        let ref_mut = rt_map.entry('a').or_insert(2);
+        rt_map.borrow_many_mut([&'a', &'a']);
+    }
 
-        assert_eq!(1, *ref_mut);
-        drop(ref_mut);
+    #[test]
+    #[should_panic(
+        expected = "Expected distinct keys, but `try_borrow_many_mut` was passed a key more than \
+                    once."
+    )]
+    fn try_borrow_many_mut_duplicate_keys_panics() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
 
-        rt_map.remove(&'a');
+        let _result = rt_map.try_borrow_many_mut([&'a', &'b', &'a']);
+    }
 
-        let ref_mut = rt_map.entry('a').or_insert_with(|| 3);
+    #[test]
+    fn try_borrow2_mut_same_key_returns_borrow_conflict_mut() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
 
-        assert_eq!(3, *ref_mut);
-        drop(ref_mut);
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictMut),
+            rt_map.try_borrow2_mut(&'a', &'a').map(|_| ())
+        );
+        assert!(rt_map.try_borrow_mut(&'a').is_ok());
     }
 
     #[test]
-    fn get_mut_returns_mutable_reference_to_value() {
+    fn try_borrow3_mut_borrows_all_values() {
         let mut rt_map = RtMap::new();
-        rt_map.insert('a', Value(1));
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+        rt_map.insert('c', 3);
 
-        let value = rt_map.get_mut(&'a');
+        let (mut a, b, c) = rt_map.try_borrow3_mut(&'a', &'b', &'c').unwrap();
+        *a += *b + *c;
+        drop((a, b, c));
 
-        assert!(value.is_some());
+        assert_eq!(6, *rt_map.borrow(&'a'));
+    }
 
-        if let Some(value) = value {
-            *value = Value(2);
-        }
+    #[test]
+    fn try_borrow_and_borrow_mut_returns_borrow_fail() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
 
-        let value = rt_map.get_mut(&'a').map(|value| value.0);
+        let _b = rt_map.borrow(&'b');
 
-        assert_eq!(Some(2), value);
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictMut),
+            rt_map.try_borrow_and_borrow_mut(&'a', &'b').map(|_| ())
+        );
     }
 
     #[test]
-    #[should_panic(expected = "but it was already borrowed")]
-    fn read_write_fails() {
+    fn get_disjoint_mut_returns_values_for_present_keys() {
         let mut rt_map = RtMap::new();
-        rt_map.insert('a', Res);
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
 
-        let _read = rt_map.borrow(&'a');
-        let _write = rt_map.borrow_mut(&'a');
+        let [a, b] = rt_map.get_disjoint_mut([&'a', &'b']);
+        *a.unwrap() += 10;
+        *b.unwrap() += 20;
+
+        assert_eq!(11, *rt_map.borrow(&'a'));
+        assert_eq!(22, *rt_map.borrow(&'b'));
     }
 
     #[test]
-    #[should_panic(expected = "but it was already borrowed mutably")]
-    fn write_read_fails() {
+    fn get_disjoint_mut_returns_none_for_missing_keys() {
         let mut rt_map = RtMap::new();
-        rt_map.insert('a', Res);
+        rt_map.insert('a', 1);
+        rt_map.insert('c', 3);
 
-        let _write = rt_map.borrow_mut(&'a');
-        let _read = rt_map.borrow(&'a');
+        let [a, b, c] = rt_map.get_disjoint_mut([&'a', &'b', &'c']);
+
+        assert_eq!(Some(&mut 1), a);
+        assert_eq!(None, b);
+        assert_eq!(Some(&mut 3), c);
     }
 
     #[test]
-    fn remove_insert() {
+    #[should_panic(expected = "duplicate keys found")]
+    fn get_disjoint_mut_same_key_twice_panics() {
         let mut rt_map = RtMap::new();
-        rt_map.insert('a', Res);
+        rt_map.insert('a', 1);
 
-        assert!(rt_map.contains_key(&'a'));
+        rt_map.get_disjoint_mut([&'a', &'b', &'a']);
+    }
 
-        rt_map.remove(&'a').unwrap();
+    #[test]
+    fn with_and_with_mut_release_borrow_after_closure() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
 
-        assert!(!rt_map.contains_key(&'a'));
+        assert_eq!(Ok(()), rt_map.with_mut(&'a', |a| *a += 1));
+        assert_eq!(Ok(2), rt_map.with(&'a', |a| *a));
+        assert!(rt_map.try_borrow_mut(&'a').is_ok());
+    }
 
-        rt_map.insert('a', Res);
+    #[test]
+    fn with_mut_returns_borrow_fail() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
 
-        assert!(rt_map.contains_key(&'a'));
+        let _a = rt_map.borrow(&'a');
+
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictMut),
+            rt_map.with_mut(&'a', |a| *a += 1)
+        );
+        assert_eq!(Err(BorrowFail::ValueNotFound), rt_map.with(&'b', |b| *b));
     }
 
     #[test]
-    #[should_panic(expected = "Expected to borrow `'a'`, but it does not exist.")]
-    fn borrow_before_insert_panics() {
-        let rt_map = RtMap::<char, i32>::new();
+    fn replace_returns_old_value() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
 
-        rt_map.borrow(&'a');
+        assert_eq!(Ok(1), rt_map.replace(&'a', 2));
+        assert_eq!(2, *rt_map.borrow(&'a'));
+        assert_eq!(Err(BorrowFail::ValueNotFound), rt_map.replace(&'b', 2));
     }
 
     #[test]
-    #[should_panic(expected = "Expected to borrow `'a'`, but it does not exist.")]
-    fn borrow_mut_before_insert_panics() {
-        let rt_map = RtMap::<char, i32>::new();
+    fn replace_borrowed_value_returns_borrow_conflict_mut() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
 
-        rt_map.borrow_mut(&'a');
+        let _a = rt_map.borrow(&'a');
+
+        assert_eq!(Err(BorrowFail::BorrowConflictMut), rt_map.replace(&'a', 2));
     }
 
     #[test]
-    fn borrow_mut_try_borrow_returns_borrow_conflict_imm() {
+    fn take_leaves_default_value() {
         let mut rt_map = RtMap::new();
-        rt_map.insert('a', Res);
+        rt_map.insert('a', String::from("a"));
 
-        let _res = rt_map.borrow_mut(&'a');
+        assert_eq!(Ok(String::from("a")), rt_map.take(&'a'));
+        assert_eq!("", *rt_map.borrow(&'a'));
+        assert_eq!(Err(BorrowFail::ValueNotFound), rt_map.take(&'b'));
+    }
 
-        assert_eq!(Err(BorrowFail::BorrowConflictImm), rt_map.try_borrow(&'a'));
+    #[test]
+    fn swap_values_swaps_values() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        assert_eq!(Ok(()), rt_map.swap_values(&'a', &'b'));
+        assert_eq!(2, *rt_map.borrow(&'a'));
+        assert_eq!(1, *rt_map.borrow(&'b'));
     }
 
     #[test]
-    fn borrow_try_borrow_mut_returns_borrow_conflict_mut() {
+    fn swap_values_same_key_does_nothing() {
         let mut rt_map = RtMap::new();
-        rt_map.insert('a', Res);
+        rt_map.insert('a', 1);
 
-        let _res = rt_map.borrow(&'a');
+        assert_eq!(Ok(()), rt_map.swap_values(&'a', &'a'));
+        assert_eq!(1, *rt_map.borrow(&'a'));
 
+        let a = rt_map.borrow(&'a');
         assert_eq!(
             Err(BorrowFail::BorrowConflictMut),
-            rt_map.try_borrow_mut(&'a')
+            rt_map.swap_values(&'a', &'a')
+        );
+        drop(a);
+
+        assert_eq!(
+            Err(BorrowFail::ValueNotFound),
+            rt_map.swap_values(&'a', &'b')
         );
+        assert!(rt_map.try_borrow_mut(&'a').is_ok());
     }
 
     #[test]
-    fn borrow_mut_borrow_mut_returns_borrow_conflict_mut() {
+    fn get_cloned_returns_clone_of_value() {
         let mut rt_map = RtMap::new();
-        rt_map.insert('a', Res);
+        rt_map.insert('a', 1);
 
-        let _res = rt_map.borrow_mut(&'a');
+        assert_eq!(Some(1), rt_map.get_cloned(&'a'));
+        assert_eq!(None, rt_map.get_cloned(&'b'));
+        assert!(rt_map.try_borrow_mut(&'a').is_ok());
+    }
+
+    #[test]
+    fn try_get_cloned_returns_borrow_fail() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let _a = rt_map.borrow_mut(&'a');
 
         assert_eq!(
-            Err(BorrowFail::BorrowConflictMut),
-            rt_map.try_borrow_mut(&'a')
+            Err(BorrowFail::BorrowConflictImm),
+            rt_map.try_get_cloned(&'a')
         );
+        assert_eq!(Err(BorrowFail::ValueNotFound), rt_map.try_get_cloned(&'b'));
     }
 
     #[test]
-    fn try_borrow_before_insert_returns_value_not_found() {
-        let rt_map = RtMap::<char, Res>::new();
+    #[should_panic(expected = "but it was already borrowed mutably")]
+    fn get_cloned_while_borrowed_mutably_panics() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
 
-        assert_eq!(Err(BorrowFail::ValueNotFound), rt_map.try_borrow(&'a'));
+        let _a = rt_map.borrow_mut(&'a');
+
+        rt_map.get_cloned(&'a');
     }
 
     #[test]
-    fn try_borrow_mut_before_insert_returns_value_not_found() {
-        let rt_map = RtMap::<char, Res>::new();
+    fn remove_entry_returns_stored_key() {
+        let mut rt_map = RtMap::<String, u32>::new();
+        rt_map.insert(String::from("a"), 1);
 
-        assert_eq!(Err(BorrowFail::ValueNotFound), rt_map.try_borrow_mut(&'a'));
+        assert_eq!(Some((String::from("a"), 1)), rt_map.remove_entry("a"));
+        assert_eq!(None, rt_map.remove_entry("a"));
+        assert!(rt_map.is_empty());
     }
 
     #[test]
-    #[should_panic(expected = "Expected to borrow `'a'`, but it does not exist.")]
-    fn borrow_before_insert_panics_value_not_found() {
-        let rt_map = RtMap::<char, Res>::new();
+    fn into_keys_and_into_values() {
+        let rt_map = RtMap::from([('a', 1), ('b', 2)]);
+
+        let mut keys = rt_map.clone().into_keys().collect::<Vec<_>>();
+        keys.sort();
+        let mut values = rt_map.into_values().collect::<Vec<_>>();
+        values.sort();
+
+        assert_eq!(vec!['a', 'b'], keys);
+        assert_eq!(vec![1, 2], values);
+    }
+
+    #[test]
+    fn raw_entry_from_key_hashed_nocheck_returns_stored_key() {
+        let mut rt_map = RtMap::<String, u32>::new();
+        rt_map.insert(String::from("a"), 1);
+
+        let hash = rt_map.hasher().hash_one("a");
+        let (k, a) = rt_map
+            .raw_entry()
+            .from_key_hashed_nocheck(hash, "a")
+            .unwrap();
+
+        assert_eq!("a", k);
+        assert_eq!(1, *a);
+        assert_eq!(
+            Err(BorrowFail::ValueNotFound),
+            rt_map.raw_entry().from_key("b").map(|(_k, v)| *v)
+        );
+    }
+
+    #[test]
+    fn raw_entry_from_key_mutably_borrowed_returns_borrow_conflict_imm() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let _a = rt_map.borrow_mut(&'a');
+
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictImm),
+            rt_map.raw_entry().from_key(&'a').map(|(_k, v)| *v)
+        );
+    }
+
+    #[test]
+    fn raw_entry_mut_or_insert_returns_existing_value() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let (k, mut a) = rt_map.raw_entry_mut().from_key(&'a').or_insert('a', 0);
+        *a += 1;
+
+        assert_eq!('a', *k);
+        drop(a);
+        assert_eq!(2, *rt_map.borrow(&'a'));
+    }
+
+    #[test]
+    #[should_panic(expected = "but the map is at its maximum of 1 entries.")]
+    fn raw_entry_mut_or_insert_new_key_at_max_entries_panics() {
+        let mut rt_map = RtMap::builder().with_max_entries(1).build();
+        rt_map.insert('a', 1);
+
+        let _ = rt_map.raw_entry_mut().from_key(&'a').or_insert('a', 0);
+        let _ = rt_map.raw_entry_mut().from_key(&'b').or_insert('b', 0);
+    }
+
+    #[test]
+    fn reserve_and_shrink_to() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        rt_map.reserve(100);
+        assert!(rt_map.capacity() >= 101);
+
+        rt_map.shrink_to(10);
+        assert!(rt_map.capacity() >= 10);
+        assert!(rt_map.capacity() < 101);
+
+        rt_map.shrink_to_fit();
+        assert!(rt_map.capacity() >= 1);
+        assert!(rt_map.capacity() < 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected to borrow a value with a `char` key, \
+        but it was already borrowed mutably.")]
+    fn borrow_while_borrowed_mutably_panics_with_key_type() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
 
+        let _a = rt_map.borrow_mut(&'a');
         rt_map.borrow(&'a');
     }
 
     #[test]
-    #[should_panic(expected = "Expected to borrow `'a'`, but it does not exist.")]
-    fn borrow_mut_before_insert_panics_value_not_found() {
-        let rt_map = RtMap::<char, Res>::new();
+    #[should_panic(expected = "Expected to mutably borrow a value with a `char` key, \
+        but it was already borrowed immutably.")]
+    fn borrow_mut_while_borrowed_panics_with_key_type() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
 
+        let _a = rt_map.borrow(&'a');
         rt_map.borrow_mut(&'a');
     }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow the value for `'b'`, but the key does not exist."
+    )]
+    fn borrow_keyed_missing_key_panics_with_key() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        rt_map.borrow_keyed(&'b');
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected to mutably borrow the value for `'a'`, \
+        but it was already borrowed immutably.")]
+    fn borrow_mut_keyed_while_borrowed_panics_with_key() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let _a = rt_map.borrow(&'a');
+        rt_map.borrow_mut_keyed(&'a');
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected to borrow a value with a `char` key, \
+        but it was already borrowed mutably.")]
+    fn get_many_while_borrowed_mutably_panics_with_key_type() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let _b = rt_map.borrow_mut(&'b');
+        let _values = rt_map.get_many([&'a', &'b']);
+    }
+
+    #[test]
+    fn try_borrow_mut_keyed_returns_key_with_borrow_fail() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let _a = rt_map.borrow(&'a');
+
+        assert_eq!(
+            Err(KeyedBorrowFail {
+                key: 'a',
+                borrow_fail: BorrowFail::BorrowConflictMut,
+            }),
+            rt_map.try_borrow_mut_keyed(&'a').map(|_| ())
+        );
+        assert_eq!(Ok(1), rt_map.try_borrow_keyed(&'a').map(|a| *a));
+    }
+
+    #[test]
+    fn merge_from_resolves_conflicts_in_place() {
+        let mut a = RtMap::from([('a', vec![1]), ('b', vec![2])]);
+        let b = RtMap::from([('b', vec![3]), ('c', vec![4])]);
+
+        let mut resolved_keys = Vec::new();
+        a.merge_from(b, |k, v_a, mut v_b| {
+            resolved_keys.push(*k);
+            v_a.append(&mut v_b);
+        });
+
+        assert_eq!(vec!['b'], resolved_keys);
+        assert_eq!(vec![1], *a.borrow(&'a'));
+        assert_eq!(vec![2, 3], *a.borrow(&'b'));
+        assert_eq!(vec![4], *a.borrow(&'c'));
+    }
+
+    #[test]
+    fn diff_returns_empty_change_set_for_equal_maps() {
+        let a = RtMap::from([('a', 1), ('b', 2)]);
+
+        assert!(a.diff(&a.clone()).is_empty());
+    }
+
+    #[test]
+    fn try_diff_returns_borrow_fail() {
+        let a = RtMap::from([('a', 1)]);
+        let b = RtMap::from([('a', 2)]);
+
+        let _a = b.borrow_mut(&'a');
+
+        assert_eq!(Err(BorrowFail::BorrowConflictImm), a.try_diff(&b));
+    }
+
+    #[test]
+    fn apply_removes_before_adding_at_max_entries() {
+        let mut a = RtMap::builder().with_max_entries(2).build();
+        a.insert('a', 1);
+        a.insert('b', 2);
+        let b = RtMap::from([('b', 3), ('c', 4)]);
+
+        let change_set = a.diff(&b);
+        a.apply(change_set);
+
+        assert_eq!(b, a);
+        assert_eq!(Some(2), a.max_entries());
+    }
+
+    #[test]
+    fn try_apply_over_max_entries_leaves_map_unchanged() {
+        let mut a = RtMap::builder().with_max_entries(2).build();
+        a.insert('a', 1);
+        a.insert('b', 2);
+        let b = RtMap::from([('a', 10), ('c', 3), ('d', 4)]);
+
+        let change_set = a.diff(&b);
+        let result = a.try_apply(change_set);
+
+        assert_eq!(Some(2), result.err().map(|e| e.max_entries()));
+        assert_eq!(RtMap::from([('a', 1), ('b', 2)]), a);
+    }
+
+    #[test]
+    fn flush_removals_removes_deferred_keys_once() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let b = rt_map.borrow(&'b');
+        rt_map.remove_deferred('a');
+        rt_map.remove_deferred('a');
+        rt_map.remove_deferred('c');
+        drop(b);
+
+        assert_eq!(2, rt_map.len());
+        assert_eq!(1, rt_map.flush_removals());
+        assert_eq!(0, rt_map.flush_removals());
+        assert!(!rt_map.contains_key(&'a'));
+        assert!(rt_map.contains_key(&'b'));
+    }
+
+    #[test]
+    fn flush_removals_removes_value_inserted_after_queueing() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        rt_map.remove_deferred('a');
+        rt_map.remove(&'a');
+        rt_map.insert('a', 2);
+
+        assert_eq!(1, rt_map.flush_removals());
+        assert!(!rt_map.contains_key(&'a'));
+    }
+
+    #[test]
+    fn clear_discards_deferred_removals() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        rt_map.remove_deferred('a');
+        rt_map.clear();
+        rt_map.insert('a', 2);
+
+        assert_eq!(0, rt_map.flush_removals());
+        assert_eq!(2, *rt_map.borrow(&'a'));
+    }
+
+    #[test]
+    fn try_clone_keeps_deferred_removals() {
+        let rt_map = RtMap::from([('a', 1), ('b', 2)]);
+        rt_map.remove_deferred('a');
+
+        let mut rt_map_clone = rt_map.try_clone().unwrap();
+
+        assert_eq!(1, rt_map_clone.flush_removals());
+        assert!(!rt_map_clone.contains_key(&'a'));
+    }
+
+    #[test]
+    fn stats_returns_len_while_values_are_borrowed() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let _a = rt_map.borrow(&'a');
+        let _b = rt_map.borrow_mut(&'b');
+
+        assert_eq!(2, rt_map.stats().len);
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn stats_includes_borrow_stats() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+
+        let _a = rt_map.try_borrow_mut(&'a');
+        let _conflict = rt_map.try_borrow(&'a');
+
+        assert_eq!(rt_map.borrow_stats(), rt_map.stats().borrow_stats);
+        assert_eq!(1, rt_map.stats().borrow_stats.conflicts);
+    }
 }
@@ -0,0 +1,168 @@
+use std::{
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+    sync::Mutex,
+};
+
+use hashbrown::HashMap;
+
+use crate::{DefaultHashBuilder, RtMap, ShrinkPolicy};
+
+/// Builder for an [`RtMap`] with non-default configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_map::{RtMap, ShrinkPolicy};
+///
+/// let rt_map = RtMap::<char, u32>::builder()
+///     .with_capacity(100)
+///     .with_shrink_policy(ShrinkPolicy::Fraction(4))
+///     .build();
+///
+/// assert!(rt_map.capacity() >= 100);
+/// ```
+#[derive(Debug)]
+pub struct RtMapBuilder<K, V, S = DefaultHashBuilder> {
+    /// Number of entries to allocate space for.
+    capacity: usize,
+    /// When to release unused capacity after removals.
+    shrink_policy: ShrinkPolicy,
+    /// Maximum number of entries the map may hold.
+    max_entries: Option<usize>,
+    /// Hash builder used to hash keys.
+    hash_builder: S,
+    /// Marker for the key and value types.
+    marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> RtMapBuilder<K, V>
+where
+    K: Hash + Eq,
+{
+    /// Returns a new `RtMapBuilder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<K, V, S> RtMapBuilder<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    /// Sets the hash builder used to hash keys.
+    ///
+    /// Defaults to [`DefaultHashBuilder`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+    ///
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::<char, u32>::builder()
+    ///     .with_hasher(BuildHasherDefault::<DefaultHasher>::default())
+    ///     .build();
+    /// rt_map.insert('a', 1);
+    ///
+    /// assert_eq!(1, *rt_map.borrow(&'a'));
+    /// ```
+    pub fn with_hasher<S2>(self, hash_builder: S2) -> RtMapBuilder<K, V, S2>
+    where
+        S2: BuildHasher,
+    {
+        let Self {
+            capacity,
+            shrink_policy,
+            max_entries,
+            hash_builder: _,
+            marker,
+        } = self;
+
+        RtMapBuilder {
+            capacity,
+            shrink_policy,
+            max_entries,
+            hash_builder,
+            marker,
+        }
+    }
+
+    /// Sets the initial capacity of the map.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets when the map releases unused capacity after removals.
+    ///
+    /// Defaults to [`ShrinkPolicy::Never`].
+    pub fn with_shrink_policy(mut self, shrink_policy: ShrinkPolicy) -> Self {
+        self.shrink_policy = shrink_policy;
+        self
+    }
+
+    /// Sets the maximum number of entries the map may hold.
+    ///
+    /// Once the map holds this many entries, inserting a new key through
+    /// [`RtMap::checked_insert`] or [`RtMap::checked_entry`] returns a
+    /// [`CapacityExceeded`] error, and inserting through other methods
+    /// panics. Existing keys may still be updated.
+    ///
+    /// Defaults to no limit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::builder().with_max_entries(1).build();
+    ///
+    /// assert_eq!(Ok(None), rt_map.checked_insert('a', 1));
+    /// assert_eq!(Ok(Some(1)), rt_map.checked_insert('a', 2));
+    /// assert!(rt_map.checked_insert('b', 3).is_err());
+    /// ```
+    ///
+    /// [`CapacityExceeded`]: crate::CapacityExceeded
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Returns the configured `RtMap`.
+    pub fn build(self) -> RtMap<K, V, S> {
+        let Self {
+            capacity,
+            shrink_policy,
+            max_entries,
+            hash_builder,
+            marker: PhantomData,
+        } = self;
+
+        RtMap {
+            inner: HashMap::with_capacity_and_hasher(capacity, hash_builder),
+            shrink_policy,
+            max_entries,
+            deferred_removals: Mutex::default(),
+            #[cfg(feature = "stats")]
+            borrow_counters: Default::default(),
+        }
+    }
+}
+
+impl<K, V, S> Default for RtMapBuilder<K, V, S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Self {
+            capacity: 0,
+            shrink_policy: ShrinkPolicy::default(),
+            max_entries: None,
+            hash_builder: S::default(),
+            marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+/// Creates an [`RtMap`] containing the given key-value pairs.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_map::rt_map;
+///
+/// let rt_map = rt_map! {
+///     'a' => 1,
+///     'b' => 2,
+/// };
+///
+/// assert_eq!(1, *rt_map.borrow(&'a'));
+/// assert_eq!(2, *rt_map.borrow(&'b'));
+/// ```
+///
+/// [`RtMap`]: crate::RtMap
+#[macro_export]
+macro_rules! rt_map {
+    ($($k:expr => $v:expr),* $(,)?) => {
+        $crate::RtMap::from([$(($k, $v)),*])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RtMap;
+
+    #[test]
+    fn rt_map_inserts_pairs() {
+        let rt_map = rt_map! { 'a' => 1, 'b' => 2 };
+
+        assert_eq!(2, rt_map.len());
+        assert_eq!(1, *rt_map.borrow(&'a'));
+    }
+
+    #[test]
+    fn rt_map_empty() {
+        let rt_map: RtMap<char, u32> = rt_map! {};
+
+        assert!(rt_map.is_empty());
+    }
+}
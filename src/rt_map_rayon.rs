@@ -0,0 +1,81 @@
+use rayon::iter::ParallelIterator;
+use rt_ref::Cell;
+
+use crate::RtMap;
+
+impl<K, V, S> RtMap<K, V, S>
+where
+    K: Send,
+    V: Send,
+{
+    /// Removes all entries from the map, returning them as a parallel
+    /// iterator of owned key-value pairs.
+    ///
+    /// This is the parallel counterpart of [`drain`], for maps large enough
+    /// that tearing them down sequentially is a bottleneck. The map keeps its
+    /// allocated memory, regardless of its [`ShrinkPolicy`]. If the iterator
+    /// is dropped before it is fully consumed, the remaining entries are
+    /// still removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rayon::iter::ParallelIterator;
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::new();
+    /// rt_map.insert('a', 1);
+    /// rt_map.insert('b', 2);
+    ///
+    /// let sum: u32 = rt_map.par_drain().map(|(_k, v)| v).sum();
+    /// assert_eq!(3, sum);
+    /// assert!(rt_map.is_empty());
+    /// ```
+    ///
+    /// [`ShrinkPolicy`]: crate::ShrinkPolicy
+    /// [`drain`]: Self::drain
+    pub fn par_drain(&mut self) -> impl ParallelIterator<Item = (K, V)> + '_ {
+        self.inner
+            .par_drain()
+            .map(|(k, cell)| (k, Cell::into_inner(cell)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::iter::ParallelIterator;
+
+    use crate::{RtMap, ShrinkPolicy};
+
+    #[test]
+    fn par_drain_returns_all_entries_and_keeps_capacity() {
+        let mut rt_map = (0..1000u32).map(|n| (n, n * 2)).collect::<RtMap<_, _>>();
+        let capacity = rt_map.capacity();
+
+        let mut entries = rt_map.par_drain().collect::<Vec<(u32, u32)>>();
+        entries.sort_unstable();
+
+        assert_eq!(
+            (0..1000u32).map(|n| (n, n * 2)).collect::<Vec<_>>(),
+            entries
+        );
+        assert!(rt_map.is_empty());
+        assert_eq!(capacity, rt_map.capacity());
+    }
+
+    #[test]
+    fn par_drain_does_not_apply_shrink_policy() {
+        let mut rt_map = RtMap::builder()
+            .with_shrink_policy(ShrinkPolicy::Fraction(4))
+            .build();
+        (0..1000u32).for_each(|n| {
+            rt_map.insert(n, n);
+        });
+        let capacity = rt_map.capacity();
+
+        rt_map.par_drain().for_each(drop);
+
+        assert!(rt_map.is_empty());
+        assert_eq!(capacity, rt_map.capacity());
+    }
+}
@@ -0,0 +1,253 @@
+use std::{
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{MapAccess, Visitor},
+    ser::{self, SerializeMap},
+};
+
+use crate::{BorrowPolicy, Ref, RtMap};
+
+/// Maximum number of entries to allocate space for up front when
+/// deserializing, so that a malicious length hint cannot exhaust memory.
+const DESERIALIZE_CAPACITY_MAX: usize = 4096;
+
+impl<K, V, S> RtMap<K, V, S>
+where
+    K: Hash + Eq + Serialize,
+    V: Serialize,
+    S: BuildHasher,
+{
+    /// Serializes the map's entries as a map, handling values that are being
+    /// accessed mutably according to `borrow_policy`.
+    ///
+    /// Every value is borrowed immutably before the first entry is written,
+    /// and the borrows are held until serialization finishes. Values that are
+    /// only borrowed immutably elsewhere are serialized as usual.
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error if a value is being accessed mutably, and
+    ///   `borrow_policy` is [`BorrowPolicy::Error`].
+    /// * Returns the serializer's error if writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::{BorrowPolicy, RtMap};
+    /// use serde::ser::{Serialize, Serializer};
+    ///
+    /// struct Skipping<'a>(&'a RtMap<char, u32>);
+    ///
+    /// impl Serialize for Skipping<'_> {
+    ///     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    ///     where
+    ///         S: Serializer,
+    ///     {
+    ///         self.0.serialize_with_policy(serializer, BorrowPolicy::Skip)
+    ///     }
+    /// }
+    /// ```
+    pub fn serialize_with_policy<Ser>(
+        &self,
+        serializer: Ser,
+        borrow_policy: BorrowPolicy,
+    ) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        // Values are borrowed first, so that the number of entries is known
+        // before the map is started.
+        let entries = self
+            .try_iter()
+            .filter_map(|(k, v)| match (v, borrow_policy) {
+                (Ok(v), _) => Some(Ok((k, v))),
+                (Err(_borrow_fail), BorrowPolicy::Error) => Some(Err(ser::Error::custom(
+                    "Failed to serialize the map, as a value is being accessed mutably.",
+                ))),
+                (Err(_borrow_fail), BorrowPolicy::Skip) => None,
+            })
+            .collect::<Result<Vec<(&K, Ref<'_, V>)>, Ser::Error>>()?;
+
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        entries
+            .iter()
+            .try_for_each(|(k, v)| map.serialize_entry(k, &**v))?;
+        map.end()
+    }
+}
+
+/// Serializes the map's entries as a map, borrowing each value immutably.
+///
+/// Values that are being accessed mutably cannot be read, so serialization
+/// fails with an error if the map holds one. Use
+/// [`RtMap::serialize_with_policy`] to skip those entries instead.
+impl<K, V, S> Serialize for RtMap<K, V, S>
+where
+    K: Hash + Eq + Serialize,
+    V: Serialize,
+    S: BuildHasher,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        self.serialize_with_policy(serializer, BorrowPolicy::Error)
+    }
+}
+
+/// Deserializes a map into an `RtMap` with the default configuration.
+///
+/// If a key appears more than once, the last value is kept.
+impl<'de, K, V, S> Deserialize<'de> for RtMap<K, V, S>
+where
+    K: Hash + Eq + Deserialize<'de>,
+    V: Deserialize<'de>,
+    S: BuildHasher + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(RtMapVisitor(PhantomData))
+    }
+}
+
+/// Builds an `RtMap` from a serialized map.
+struct RtMapVisitor<K, V, S>(PhantomData<RtMap<K, V, S>>);
+
+impl<'de, K, V, S> Visitor<'de> for RtMapVisitor<K, V, S>
+where
+    K: Hash + Eq + Deserialize<'de>,
+    V: Deserialize<'de>,
+    S: BuildHasher + Default,
+{
+    type Value = RtMap<K, V, S>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A>(self, mut map_access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let capacity = map_access
+            .size_hint()
+            .unwrap_or(0)
+            .min(DESERIALIZE_CAPACITY_MAX);
+        let mut rt_map = RtMap::with_capacity_and_hasher(capacity, S::default());
+        while let Some((k, v)) = map_access.next_entry()? {
+            rt_map.insert(k, v);
+        }
+
+        Ok(rt_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Serialize, Serializer};
+    use serde_test::{
+        Token, assert_de_tokens, assert_ser_tokens, assert_ser_tokens_error, assert_tokens,
+    };
+
+    use crate::{BorrowPolicy, RtMap};
+
+    /// Serializes a map with [`BorrowPolicy::Skip`].
+    struct Skipping<'a>(&'a RtMap<char, u32>);
+
+    impl Serialize for Skipping<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            self.0.serialize_with_policy(serializer, BorrowPolicy::Skip)
+        }
+    }
+
+    #[test]
+    fn serialize_and_deserialize_entries() {
+        let rt_map = RtMap::from([('a', 1u32)]);
+
+        assert_tokens(
+            &rt_map,
+            &[
+                Token::Map { len: Some(1) },
+                Token::Char('a'),
+                Token::U32(1),
+                Token::MapEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn deserialize_keeps_last_value_for_duplicate_keys() {
+        let rt_map = RtMap::from([('a', 2u32), ('b', 3)]);
+
+        assert_de_tokens(
+            &rt_map,
+            &[
+                Token::Map { len: Some(3) },
+                Token::Char('a'),
+                Token::U32(1),
+                Token::Char('b'),
+                Token::U32(3),
+                Token::Char('a'),
+                Token::U32(2),
+                Token::MapEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn serialize_immutably_borrowed_value() {
+        let rt_map = RtMap::from([('a', 1u32)]);
+
+        let _a = rt_map.borrow(&'a');
+
+        assert_tokens(
+            &rt_map,
+            &[
+                Token::Map { len: Some(1) },
+                Token::Char('a'),
+                Token::U32(1),
+                Token::MapEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn serialize_mutably_borrowed_value_returns_error() {
+        let rt_map = RtMap::from([('a', 1u32)]);
+
+        let _a = rt_map.borrow_mut(&'a');
+
+        assert_ser_tokens_error(
+            &rt_map,
+            &[],
+            "Failed to serialize the map, as a value is being accessed mutably.",
+        );
+    }
+
+    #[test]
+    fn serialize_with_skip_policy_leaves_out_mutably_borrowed_values() {
+        let rt_map = RtMap::from([('a', 1u32), ('b', 2)]);
+
+        let _b = rt_map.borrow_mut(&'b');
+
+        assert_ser_tokens(
+            &Skipping(&rt_map),
+            &[
+                Token::Map { len: Some(1) },
+                Token::Char('a'),
+                Token::U32(1),
+                Token::MapEnd,
+            ],
+        );
+    }
+}
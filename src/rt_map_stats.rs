@@ -0,0 +1,19 @@
+#[cfg(feature = "stats")]
+use crate::BorrowStats;
+
+/// Occupancy and borrow statistics of an [`RtMap`].
+///
+/// Retrieved with [`RtMap::stats`]. The number of values that are currently
+/// borrowed is not included, as it cannot be read without briefly borrowing
+/// each value, which would make other threads' borrows fail.
+///
+/// [`RtMap`]: crate::RtMap
+/// [`RtMap::stats`]: crate::RtMap::stats
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RtMapStats {
+    /// Number of entries in the map.
+    pub len: usize,
+    /// Cumulative counts of `try_borrow` and `try_borrow_mut` outcomes.
+    #[cfg(feature = "stats")]
+    pub borrow_stats: BorrowStats,
+}
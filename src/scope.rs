@@ -0,0 +1,125 @@
+use std::{
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use hashbrown::Equivalent;
+use rt_ref::{BorrowFail, Ref, RefMut};
+
+use crate::{DefaultHashBuilder, RtMap};
+
+/// Handle to borrow values from an [`RtMap`] within [`RtMap::scope`].
+///
+/// Borrows made through the handle cannot outlive the scope's closure, so
+/// they are always dropped when the closure returns.
+pub struct Scope<'scope, 'map: 'scope, K, V, S = DefaultHashBuilder> {
+    /// The map to borrow from.
+    rt_map: &'map RtMap<K, V, S>,
+    /// Makes `'scope` invariant, so borrows cannot be extended beyond it.
+    marker: PhantomData<fn(&'scope ()) -> &'scope ()>,
+}
+
+impl<'scope, 'map, K, V, S> Scope<'scope, 'map, K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    /// Returns a new `Scope`.
+    pub(crate) fn new(rt_map: &'map RtMap<K, V, S>) -> Self {
+        Self {
+            rt_map,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// See [`RtMap::borrow`].
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the resource doesn't exist.
+    /// * Panics if the resource is being accessed mutably.
+    #[track_caller]
+    pub fn borrow<Q>(&'scope self, k: &Q) -> Ref<'scope, V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.rt_map.borrow(k)
+    }
+
+    /// Returns a reference to the value if it exists and is not mutably
+    /// borrowed.
+    ///
+    /// See [`RtMap::try_borrow`].
+    pub fn try_borrow<Q>(&'scope self, k: &Q) -> Result<Ref<'scope, V>, BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.rt_map.try_borrow(k)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// See [`RtMap::borrow_mut`].
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the resource doesn't exist.
+    /// * Panics if the resource is already accessed.
+    #[track_caller]
+    pub fn borrow_mut<Q>(&'scope self, k: &Q) -> RefMut<'scope, V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.rt_map.borrow_mut(k)
+    }
+
+    /// Returns a mutable reference to the value if it exists and is not
+    /// borrowed.
+    ///
+    /// See [`RtMap::try_borrow_mut`].
+    pub fn try_borrow_mut<Q>(&'scope self, k: &Q) -> Result<RefMut<'scope, V>, BorrowFail>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.rt_map.try_borrow_mut(k)
+    }
+}
+
+impl<K, V, S> fmt::Debug for Scope<'_, '_, K, V, S>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope")
+            .field("rt_map", &self.rt_map)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RtMap;
+
+    #[test]
+    fn scope_borrows_are_released_when_closure_returns() {
+        let mut rt_map = RtMap::new();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let sum = rt_map.scope(|scope| {
+            let mut a = scope.borrow_mut(&'a');
+            let b = scope.borrow(&'b');
+            *a += *b;
+            *a
+        });
+
+        assert_eq!(3, sum);
+        assert!(rt_map.try_borrow_mut(&'a').is_ok());
+        assert!(rt_map.try_borrow_mut(&'b').is_ok());
+    }
+}
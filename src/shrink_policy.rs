@@ -0,0 +1,47 @@
+/// When an [`RtMap`] releases unused capacity after entries are removed.
+///
+/// Maps that temporarily hold many entries keep their peak allocation by
+/// default. Setting a shrink policy through [`RtMapBuilder`] lets long-lived
+/// maps give that memory back once they drain.
+///
+/// The policy is checked by methods that remove entries and then return,
+/// such as [`remove`], [`retain`], and [`clear`]. It is not applied by the
+/// iterators returned from [`drain`], [`extract_if`], and `par_drain`, as
+/// they hold the map's table until they are dropped. Call [`shrink_to_fit`]
+/// afterwards to release the memory.
+///
+/// [`RtMap`]: crate::RtMap
+/// [`RtMapBuilder`]: crate::RtMapBuilder
+/// [`clear`]: crate::RtMap::clear
+/// [`drain`]: crate::RtMap::drain
+/// [`extract_if`]: crate::RtMap::extract_if
+/// [`remove`]: crate::RtMap::remove
+/// [`retain`]: crate::RtMap::retain
+/// [`shrink_to_fit`]: crate::RtMap::shrink_to_fit
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ShrinkPolicy {
+    /// Never shrink automatically.
+    #[default]
+    Never,
+    /// Shrink when the number of entries falls below `capacity / n`.
+    ///
+    /// For example, `ShrinkPolicy::Fraction(4)` shrinks the map once fewer
+    /// than a quarter of its capacity is in use. The map is shrunk to twice
+    /// its length, so that a few inserts afterwards do not immediately cause
+    /// it to grow again.
+    ///
+    /// A value of `0` or `1` never shrinks.
+    Fraction(usize),
+}
+
+impl ShrinkPolicy {
+    /// Returns the capacity to shrink to, if a map with the given length and
+    /// capacity should be shrunk.
+    pub(crate) fn shrink_to(self, len: usize, capacity: usize) -> Option<usize> {
+        match self {
+            Self::Never => None,
+            Self::Fraction(n) if n > 1 && len < capacity / n => Some(len * 2),
+            Self::Fraction(_) => None,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::Accessor;
+
+/// Sequential stages of mutually non-conflicting [`Accessor`]s.
+///
+/// Accessors within a stage may borrow their values at the same time, and
+/// each stage should be run after the previous stage completes. Each
+/// accessor is placed in the stage after the last stage containing an
+/// accessor it conflicts with, so conflicting accessors keep their relative
+/// order. Planning the same accessors always produces the same plan.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_map::{AccessSet, StagePlan};
+///
+/// let access_sets = [
+///     AccessSet::new().with_write('a'),
+///     AccessSet::new().with_write('b'),
+///     AccessSet::new().with_read('a').with_read('b'),
+/// ];
+///
+/// let stage_plan = StagePlan::new(&access_sets);
+///
+/// assert_eq!(&[vec![0, 1], vec![2]], stage_plan.stages());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StagePlan {
+    /// Indices of the accessors in each stage.
+    stages: Vec<Vec<usize>>,
+}
+
+impl StagePlan {
+    /// Groups the given accessors into stages.
+    pub fn new<'a, K, A>(accessors: impl IntoIterator<Item = &'a A>) -> Self
+    where
+        K: PartialEq,
+        A: Accessor<K> + 'a,
+    {
+        let mut planned = Vec::<(&A, usize)>::new();
+        let mut stages = Vec::<Vec<usize>>::new();
+
+        accessors
+            .into_iter()
+            .enumerate()
+            .for_each(|(index, accessor)| {
+                let stage_index = planned
+                    .iter()
+                    .filter(|(earlier, _stage)| accessor.conflicts_with(*earlier))
+                    .map(|(_earlier, stage)| stage + 1)
+                    .max()
+                    .unwrap_or(0);
+
+                planned.push((accessor, stage_index));
+                match stages.get_mut(stage_index) {
+                    Some(stage) => stage.push(index),
+                    None => stages.push(vec![index]),
+                }
+            });
+
+        Self { stages }
+    }
+
+    /// Returns the indices of the accessors in each stage.
+    pub fn stages(&self) -> &[Vec<usize>] {
+        &self.stages
+    }
+
+    /// Returns the number of stages.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns `true` if there are no stages.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Returns the indices of the accessors in each stage.
+    pub fn into_inner(self) -> Vec<Vec<usize>> {
+        self.stages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StagePlan;
+    use crate::AccessSet;
+
+    #[test]
+    fn new_places_non_conflicting_accessors_in_same_stage() {
+        let access_sets = [
+            AccessSet::new().with_read('a'),
+            AccessSet::new().with_read('a').with_write('b'),
+            AccessSet::new().with_write('c'),
+        ];
+
+        let stage_plan = StagePlan::new(&access_sets);
+
+        assert_eq!(&[vec![0, 1, 2]], stage_plan.stages());
+    }
+
+    #[test]
+    fn new_places_conflicting_accessors_in_later_stages() {
+        let access_sets = [
+            AccessSet::new().with_write('a'),
+            AccessSet::new().with_write('a'),
+            AccessSet::new().with_write('b'),
+            AccessSet::new().with_read('a'),
+        ];
+
+        let stage_plan = StagePlan::new(&access_sets);
+
+        assert_eq!(&[vec![0, 2], vec![1], vec![3]], stage_plan.stages());
+    }
+
+    #[test]
+    fn new_with_no_accessors_is_empty() {
+        let stage_plan = StagePlan::new::<char, AccessSet<char>>(&[]);
+
+        assert!(stage_plan.is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use crate::{CapacityExceeded, DefaultHashBuilder, RtMap};
+
+/// Buffers changes to an [`RtMap`], applying them only when committed.
+///
+/// Returned by [`RtMap::transaction`]. Changes are applied in the order they
+/// were made when [`commit`] or [`try_commit`] is called. Dropping the
+/// transaction, or calling [`rollback`], discards them and leaves the map
+/// unchanged.
+///
+/// [`commit`]: Self::commit
+/// [`rollback`]: Self::rollback
+/// [`try_commit`]: Self::try_commit
+pub struct Transaction<'map, K, V, S = DefaultHashBuilder> {
+    /// The map to apply the changes to.
+    rt_map: &'map mut RtMap<K, V, S>,
+    /// Changes to apply, in the order they were made.
+    changes: Vec<Change<'map, K, V>>,
+}
+
+/// A buffered change to a map.
+enum Change<'map, K, V> {
+    /// Insert or replace a value.
+    Insert(K, V),
+    /// Remove a key.
+    Remove(K),
+    /// Update an existing value.
+    Update(K, Box<dyn FnOnce(&mut V) + 'map>),
+}
+
+impl<'map, K, V, S> Transaction<'map, K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    /// Returns a new `Transaction`.
+    pub(crate) fn new(rt_map: &'map mut RtMap<K, V, S>) -> Self {
+        Self {
+            rt_map,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Inserts `v` at `k` when the transaction is committed.
+    pub fn insert(&mut self, k: K, v: V) {
+        self.changes.push(Change::Insert(k, v));
+    }
+
+    /// Removes `k` when the transaction is committed.
+    pub fn remove(&mut self, k: K) {
+        self.changes.push(Change::Remove(k));
+    }
+
+    /// Runs `f` on the value at `k` when the transaction is committed.
+    ///
+    /// `f` is not run if the key is not in the map at that point.
+    pub fn update<F>(&mut self, k: K, f: F)
+    where
+        F: FnOnce(&mut V) + 'map,
+    {
+        self.changes.push(Change::Update(k, Box::new(f)));
+    }
+
+    /// Returns the number of buffered changes.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns `true` if there are no buffered changes.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Applies the buffered changes to the map.
+    ///
+    /// See [`try_commit`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inserted keys would exceed the map's [maximum number of
+    /// entries]. The changes are checked before any is applied, so the map is
+    /// unchanged when this panics.
+    ///
+    /// [`try_commit`]: Self::try_commit
+    /// [maximum number of entries]: crate::RtMapBuilder::with_max_entries
+    pub fn commit(self) {
+        if let Err(capacity_exceeded) = self.try_commit() {
+            capacity_panic!(capacity_exceeded);
+        }
+    }
+
+    /// Applies the buffered changes to the map, or discards them if they
+    /// would exceed the map's [maximum number of entries].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CapacityExceeded`] if the number of entries would exceed the
+    /// map's maximum at any point while the changes are applied. No change is
+    /// applied in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_map::RtMap;
+    ///
+    /// let mut rt_map = RtMap::builder().with_max_entries(1).build();
+    /// rt_map.insert('a', 1);
+    ///
+    /// let mut transaction = rt_map.transaction();
+    /// transaction.update('a', |a| *a += 1);
+    /// transaction.insert('b', 2);
+    /// assert!(transaction.try_commit().is_err());
+    /// assert_eq!(1, *rt_map.borrow(&'a'));
+    ///
+    /// let mut transaction = rt_map.transaction();
+    /// transaction.remove('a');
+    /// transaction.insert('b', 2);
+    /// assert!(transaction.try_commit().is_ok());
+    /// assert_eq!(2, *rt_map.borrow(&'b'));
+    /// ```
+    ///
+    /// [maximum number of entries]: crate::RtMapBuilder::with_max_entries
+    pub fn try_commit(self) -> Result<(), CapacityExceeded> {
+        self.check_max_entries()?;
+
+        let Self { rt_map, changes } = self;
+
+        changes.into_iter().for_each(|change| match change {
+            Change::Insert(k, v) => {
+                rt_map.insert(k, v);
+            }
+            Change::Remove(k) => {
+                rt_map.remove(&k);
+            }
+            Change::Update(k, f) => {
+                if let Some(v) = rt_map.get_mut(&k) {
+                    f(v);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Returns an error if applying the changes in order would exceed the
+    /// map's maximum number of entries.
+    fn check_max_entries(&self) -> Result<(), CapacityExceeded> {
+        let Some(max_entries) = self.rt_map.max_entries() else {
+            return Ok(());
+        };
+
+        // Whether each key inserted or removed so far is present after those
+        // changes.
+        let mut presence = HashMap::<&K, bool>::new();
+        let mut len = self.rt_map.len();
+        self.changes.iter().try_for_each(|change| {
+            let (k, present_after) = match change {
+                Change::Insert(k, _v) => (k, true),
+                Change::Remove(k) => (k, false),
+                Change::Update(..) => return Ok(()),
+            };
+            let present_before = presence
+                .get(k)
+                .copied()
+                .unwrap_or_else(|| self.rt_map.contains_key(k));
+            match (present_before, present_after) {
+                (false, true) if len >= max_entries => {
+                    return Err(CapacityExceeded::new(max_entries));
+                }
+                (false, true) => len += 1,
+                (true, false) => len -= 1,
+                _ => {}
+            }
+            presence.insert(k, present_after);
+
+            Ok(())
+        })
+    }
+
+    /// Discards the buffered changes, leaving the map unchanged.
+    ///
+    /// This is the same as dropping the transaction.
+    pub fn rollback(self) {}
+}
+
+impl<K, V, S> fmt::Debug for Transaction<'_, K, V, S>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transaction")
+            .field("rt_map", &self.rt_map)
+            .field("changes", &self.changes.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RtMap;
+
+    #[test]
+    fn commit_applies_changes_in_order() {
+        let mut rt_map = RtMap::from([('a', 1), ('b', 2)]);
+
+        let mut transaction = rt_map.transaction();
+        transaction.insert('c', 3);
+        transaction.remove('a');
+        transaction.update('b', |b| *b += 10);
+        transaction.update('a', |a| *a += 10);
+        assert_eq!(4, transaction.len());
+        transaction.commit();
+
+        assert_eq!(RtMap::from([('b', 12), ('c', 3)]), rt_map);
+    }
+
+    #[test]
+    fn rollback_leaves_map_unchanged() {
+        let mut rt_map = RtMap::from([('a', 1)]);
+
+        let mut transaction = rt_map.transaction();
+        transaction.insert('b', 2);
+        transaction.remove('a');
+        transaction.rollback();
+
+        let mut transaction = rt_map.transaction();
+        transaction.update('a', |a| *a += 1);
+        drop(transaction);
+
+        assert_eq!(RtMap::from([('a', 1)]), rt_map);
+    }
+
+    #[test]
+    fn try_commit_over_max_entries_leaves_map_unchanged() {
+        let mut rt_map = RtMap::builder().with_max_entries(2).build();
+        rt_map.insert('a', 1);
+
+        let mut transaction = rt_map.transaction();
+        transaction.update('a', |a| *a += 10);
+        transaction.insert('b', 2);
+        transaction.remove('b');
+        transaction.insert('c', 3);
+        transaction.insert('d', 4);
+        let result = transaction.try_commit();
+
+        assert_eq!(Some(2), result.err().map(|e| e.max_entries()));
+        assert_eq!(RtMap::from([('a', 1)]), rt_map);
+    }
+
+    #[test]
+    fn try_commit_counts_removals_before_insertions() {
+        let mut rt_map = RtMap::builder().with_max_entries(2).build();
+        rt_map.insert('a', 1);
+        rt_map.insert('b', 2);
+
+        let mut transaction = rt_map.transaction();
+        transaction.insert('a', 10);
+        transaction.remove('b');
+        transaction.insert('c', 3);
+
+        assert!(transaction.try_commit().is_ok());
+        assert_eq!(RtMap::from([('a', 10), ('c', 3)]), rt_map);
+    }
+
+    #[test]
+    #[should_panic(expected = "maximum of 1 entries")]
+    fn commit_over_max_entries_panics() {
+        let mut rt_map = RtMap::builder().with_max_entries(1).build();
+        rt_map.insert('a', 1);
+
+        let mut transaction = rt_map.transaction();
+        transaction.insert('b', 2);
+        transaction.commit();
+    }
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "derive")]
+
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+use rt_map::{BorrowFail, Ref, RefMut, RtAccess, RtMap};
+
+#[derive(RtAccess)]
+#[rt_access(key_type = char)]
+struct CharData<'a> {
+    #[rt_access(key = 'a')]
+    a: Ref<'a, u32>,
+    #[rt_access(key = 'b')]
+    b: RefMut<'a, u32>,
+}
+
+#[derive(RtAccess)]
+#[rt_access(key_type = String)]
+struct StringData<'a> {
+    #[rt_access(key = "a")]
+    a: RefMut<'a, u32>,
+}
+
+#[test]
+fn fetch_borrows_all_fields() {
+    let mut rt_map = RtMap::new();
+    rt_map.insert('a', 1);
+    rt_map.insert('b', 2);
+
+    let mut data = CharData::fetch(&rt_map).unwrap();
+    *data.b += *data.a;
+    drop(data);
+
+    assert_eq!(3, *rt_map.borrow(&'b'));
+}
+
+#[test]
+fn fetch_string_keys_with_str_literal() {
+    let mut rt_map = RtMap::new();
+    rt_map.insert(String::from("a"), 1);
+
+    let mut data = StringData::fetch(&rt_map).unwrap();
+    *data.a += 1;
+    drop(data);
+
+    assert_eq!(2, *rt_map.borrow("a"));
+}
+
+#[test]
+fn fetch_returns_borrow_fail_and_releases_earlier_borrows() {
+    let mut rt_map = RtMap::new();
+    rt_map.insert('a', 1);
+    rt_map.insert('b', 2);
+
+    let b = rt_map.borrow(&'b');
+    let result = CharData::fetch(&rt_map);
+    assert_eq!(Some(BorrowFail::BorrowConflictMut), result.err());
+    drop(b);
+
+    // `a` was released when fetching failed.
+    assert!(rt_map.try_borrow_mut(&'a').is_ok());
+}
+
+#[test]
+fn fetch_returns_value_not_found_when_key_missing() {
+    let mut rt_map = RtMap::new();
+    rt_map.insert('a', 1);
+
+    assert_eq!(
+        Some(BorrowFail::ValueNotFound),
+        CharData::fetch(&rt_map).err()
+    );
+}
+
+#[test]
+fn fetch_from_map_with_custom_hasher() {
+    let mut rt_map = RtMap::with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+    rt_map.insert('a', 1);
+    rt_map.insert('b', 2);
+
+    let mut data = CharData::fetch(&rt_map).unwrap();
+    *data.b += *data.a;
+    drop(data);
+
+    assert_eq!(3, *rt_map.borrow(&'b'));
+}